@@ -1,3 +1,7 @@
+use crate::AST;
+use crate::JsonLike;
+use crate::Located;
+use crate::Map;
 use crate::Value;
 use crate::errors::Error;
 use crate::runtime::Runtime;
@@ -23,6 +27,221 @@ pub fn search(expression: &str, root: &Value) -> Result<Value, Error> {
     Runtime::get_shared_runtime().search(expression, root)
 }
 
+/// Evaluates a JMESPath expression with the specified variables in scope.
+///
+/// This supplies context to an expression, such as the current user.
+/// Variable names may omit their leading `$`. Referencing a variable
+/// that is not bound raises an [`Error`] of kind
+/// [`UndefinedVariable`](crate::errors::Kind::UndefinedVariable).
+///
+/// Use [`AST::variables()`](crate::AST::variables()) to list
+/// the variables that an expression requires.
+///
+/// # Example
+///
+/// ```
+/// use jmespath_community as jmespath;
+/// use jmespath::{map, search_with, Map, Value};
+///
+/// let input = Value::from_json(r#"[
+///     {"id": 1, "owner": "ada"},
+///     {"id": 2, "owner": "alan"}
+/// ]"#).unwrap();
+/// let bindings = map!("user".to_string() => "alan".into());
+/// let result = search_with("[?owner == $user].id", &input, bindings).unwrap();
+///
+/// assert_eq!(Value::from_json("[2]").unwrap(), result);
+/// ```
+pub fn search_with(
+    expression: &str,
+    root: &Value,
+    bindings: Map<String, Value>,
+) -> Result<Value, Error> {
+    Runtime::get_shared_runtime().search_with(expression, root, bindings)
+}
+
+/// Evaluates a JMESPath expression against a document of any type
+/// that implements [`JsonLike`], such as [`serde_json::Value`].
+///
+/// The document is searched in place. Only the result is converted to a [`Value`].
+///
+/// # Example
+///
+/// ```
+/// use jmespath_community as jmespath;
+/// use jmespath::search_in;
+///
+/// let document = serde_json::json!({"foo": {"bar": [1, 2, 3]}});
+/// let result = search_in("foo.bar[-1]", &document).unwrap();
+///
+/// assert_eq!(3, result);
+/// ```
+pub fn search_in<J: JsonLike>(expression: &str, root: &J) -> Result<Value, Error> {
+    Runtime::get_shared_runtime().search_in(expression, root)
+}
+
+/// Evaluates a JMESPath expression and pairs each result with
+/// its location in the JSON document.
+///
+/// The elements selected by a projection are returned individually.
+/// Values constructed by the expression, _e.g_ by functions or
+/// multi-selects, are [synthetic](Located::is_synthetic()).
+///
+/// # Example
+///
+/// ```
+/// use jmespath_community as jmespath;
+/// use jmespath::{search_paths, Value};
+///
+/// let input = Value::from_json(r#"{"people": [
+///     {"name": "Ada", "age": 36},
+///     {"name": "Alan", "age": 12},
+///     {"name": "Grace", "age": 45}
+/// ]}"#).unwrap();
+///
+/// let results = search_paths("people[?age > `30`].name", &input).unwrap();
+/// let pointers: Vec<String> = results
+///     .iter()
+///     .map(|x| x.path.as_ref().unwrap().to_json_pointer())
+///     .collect();
+///
+/// assert_eq!(vec!["/people/0/name", "/people/2/name"], pointers);
+/// assert_eq!("Grace", results[1].value);
+/// ```
+pub fn search_paths<J: JsonLike>(expression: &str, root: &J) -> Result<Vec<Located>, Error> {
+    Runtime::get_shared_runtime().search_paths(expression, root)
+}
+
+/// Evaluates several parsed JMESPath expressions against the same document.
+///
+/// Sub-expressions shared between the expressions, such as a common prefix
+/// `detail.requestParameters`, are evaluated once against the root of the
+/// document. Functions are assumed to always return the same result
+/// for the same arguments.
+///
+/// Returns one result per expression, in order. An error in one
+/// expression does not prevent evaluating the others.
+///
+/// # Example
+///
+/// ```
+/// use jmespath_community as jmespath;
+/// use jmespath::{parse, search_many, Value};
+///
+/// let input = Value::from_json(r#"{"detail": {"user": "ada", "ip": "10.0.0.1"}}"#).unwrap();
+/// let asts = vec![
+///     parse("detail.user").unwrap(),
+///     parse("detail.ip").unwrap(),
+///     parse("abs(detail.user)").unwrap(),
+/// ];
+///
+/// let results = search_many(&asts, &input);
+///
+/// assert_eq!("ada", *results[0].as_ref().unwrap());
+/// assert_eq!("10.0.0.1", *results[1].as_ref().unwrap());
+/// assert!(results[2].is_err());
+/// ```
+pub fn search_many(asts: &[AST], root: &Value) -> Vec<Result<Value, Error>> {
+    Runtime::get_shared_runtime().search_many(asts, root)
+}
+
+/// Updates the values selected by a JMESPath expression in place.
+///
+/// The expression must select locations in the JSON document, using fields,
+/// indices, slices, filters and wildcard projections. Expressions that
+/// construct new values, such as function calls, arithmetic or multi-selects,
+/// are rejected with an [`Error`] of kind [`InvalidValue`](crate::errors::Kind::InvalidValue).
+///
+/// Returns the number of values that have been updated.
+///
+/// # Example
+///
+/// ```
+/// use jmespath_community as jmespath;
+/// use jmespath::{update, Value};
+///
+/// let mut config = Value::from_json(r#"{"servers": [
+///     {"host": "alpha", "port": 8080},
+///     {"host": "beta", "port": 9090}
+/// ]}"#).unwrap();
+///
+/// let count = update(&mut config, "servers[?port < `9000`].port", |_| 8443.into()).unwrap();
+///
+/// assert_eq!(1, count);
+/// assert_eq!(Value::from_json("[8443, 9090]").unwrap(), jmespath::search("servers[*].port", &config).unwrap());
+/// ```
+pub fn update(
+    root: &mut Value,
+    expression: &str,
+    f: impl FnMut(&Value) -> Value,
+) -> Result<usize, Error> {
+    Runtime::get_shared_runtime().update(root, expression, f)
+}
+
+/// Deletes the values selected by a JMESPath expression in place.
+///
+/// Selected object members are removed from their object, and selected
+/// array elements are removed from their array. See [`update()`] for the
+/// expressions that can select values.
+///
+/// Returns the number of values that have been deleted.
+///
+/// # Example
+///
+/// ```
+/// use jmespath_community as jmespath;
+/// use jmespath::{delete, Value};
+///
+/// let mut config = Value::from_json(r#"{"debug": true, "servers": ["alpha", "beta"]}"#).unwrap();
+///
+/// delete(&mut config, "debug").unwrap();
+/// delete(&mut config, "servers[?@ == 'beta']").unwrap();
+///
+/// assert_eq!(Value::from_json(r#"{"servers": ["alpha"]}"#).unwrap(), config);
+/// ```
+pub fn delete(root: &mut Value, expression: &str) -> Result<usize, Error> {
+    Runtime::get_shared_runtime().delete(root, expression)
+}
+
+/// Evaluates a JMESPath expression against any type that implements
+/// [`serde::Serialize`] and deserializes the result into the type `R`.
+///
+/// Returns an [`Error`] of kind [`InvalidType`](crate::errors::Kind::InvalidType)
+/// if the result does not match the shape of `R`.
+///
+/// # Example
+///
+/// ```
+/// use jmespath_community as jmespath;
+/// use jmespath::search_as;
+///
+/// #[derive(serde::Serialize)]
+/// struct Config {
+///     servers: Vec<Server>,
+/// }
+/// #[derive(serde::Serialize)]
+/// struct Server {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// let config = Config {
+///     servers: vec![
+///         Server { host: "alpha".to_string(), port: 8080 },
+///         Server { host: "beta".to_string(), port: 9090 },
+///     ],
+/// };
+///
+/// let ports = search_as::<Vec<u16>>("servers[*].port", &config).unwrap();
+/// assert_eq!(vec![8080, 9090], ports);
+/// ```
+pub fn search_as<R: serde::de::DeserializeOwned>(
+    expression: &str,
+    root: &(impl serde::Serialize + ?Sized),
+) -> Result<R, Error> {
+    Runtime::get_shared_runtime().search_as(expression, root)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -33,4 +252,62 @@ mod tests {
         let result = search("foo", &root).unwrap();
         assert_eq!("\"bar\"", result.to_json());
     }
+
+    #[rstest::rstest]
+    #[case("foo.bar[-1]", "3")]
+    #[case("foo.bar[1:]", "[2, 3]")]
+    #[case("foo.*[]", "[1, 2, 3]")]
+    #[case("foo.bar[?@ > `1`]", "[2, 3]")]
+    #[case("people[*].{name: name, age: age}", r#"[{"name": "Ada", "age": 36}]"#)]
+    #[case("people[0].name | length(@)", "3")]
+    #[case("let $f = foo in people[?age > length($f.bar)].name", r#"["Ada"]"#)]
+    #[case("people[0].age - length($.foo.bar)", "33")]
+    #[case("..bar", "[[1, 2, 3]]")]
+    #[case("missing.bar", "null")]
+    fn it_search_in_serde_json(#[case] expression: &str, #[case] expected: &str) {
+        let document = serde_json::json!({
+            "foo": {"bar": [1, 2, 3]},
+            "people": [{"name": "Ada", "age": 36}],
+        });
+        let expected = Value::from_json(expected).unwrap();
+        assert_eq!(expected, search_in(expression, &document).unwrap());
+        // searching a Value yields the same result
+        let root = Value::from(&document);
+        assert_eq!(expected, search(expression, &root).unwrap());
+    }
+
+    #[derive(serde::Serialize)]
+    struct Account {
+        id: u64,
+        name: &'static str,
+    }
+
+    #[test]
+    fn it_search_as() {
+        let accounts = vec![
+            Account {
+                id: 1234567890123456789,
+                name: "main",
+            },
+            Account {
+                id: 2,
+                name: "other",
+            },
+        ];
+        let ids: Vec<u64> = search_as("[*].id", &accounts).unwrap();
+        assert_eq!(vec![1234567890123456789, 2], ids);
+
+        let name: Option<String> = search_as("[?id == `2`].name | [0]", &accounts).unwrap();
+        assert_eq!(Some("other".to_string()), name);
+    }
+
+    #[test]
+    fn it_search_as_reports_type_mismatch() {
+        let accounts = vec![Account {
+            id: 1,
+            name: "main",
+        }];
+        let err = search_as::<Vec<u64>>("[*].name", &accounts).unwrap_err();
+        assert_eq!(crate::errors::Kind::InvalidType, err.kind);
+    }
 }
@@ -0,0 +1,202 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::mem::{Discriminant, discriminant};
+
+use crate::interpreter::Interpreter;
+use crate::json_like::{JsonLike, Node};
+use crate::parser::AST;
+use crate::{NodeType, Runtime, functions::ReturnValue};
+
+/// Evaluates several expressions against the same JSON document.
+///
+/// Sub-expressions that occur in more than one place are
+/// evaluated at most once against the root of the document.
+pub(crate) fn search_many<J: JsonLike>(
+    runtime: &Runtime,
+    asts: &[AST],
+    root: &J,
+) -> Vec<ReturnValue> {
+    let shared = SharedResults::new(asts);
+    let interpreter = Interpreter::new(runtime, root).sharing(&shared);
+    asts.iter().map(|ast| interpreter.evaluate(ast)).collect()
+}
+
+/// Identifies a sub-expression by its structure, regardless of its position.
+#[derive(PartialEq, Eq, Hash)]
+struct Shape {
+    kind: Discriminant<NodeType>,
+    text: String,
+    children: Vec<usize>,
+}
+
+/// Holds the results of the sub-expressions shared between several expressions.
+pub(crate) struct SharedResults<'j, J: JsonLike> {
+    /// The shape of each shared node, by address.
+    ids: HashMap<*const AST, usize>,
+    results: RefCell<HashMap<usize, Node<'j, J>>>,
+}
+impl<'j, J: JsonLike> SharedResults<'j, J> {
+    pub fn new(asts: &[AST]) -> Self {
+        let mut shapes = HashMap::new();
+        let mut nodes = Vec::new();
+        for ast in asts {
+            Self::intern(ast, &mut shapes, &mut nodes);
+        }
+        let mut counts = HashMap::new();
+        for (_, id) in &nodes {
+            *counts.entry(*id).or_insert(0) += 1;
+        }
+        let ids = nodes.into_iter().filter(|(_, id)| counts[id] > 1).collect();
+        SharedResults {
+            ids,
+            results: RefCell::new(HashMap::new()),
+        }
+    }
+    /// Returns the result of a shared node, if it has already been evaluated.
+    pub fn get(&self, ast: &AST) -> Option<Node<'j, J>> {
+        let id = self.ids.get(&(ast as *const AST))?;
+        self.results.borrow().get(id).cloned()
+    }
+    /// Records the result of a node, if it is shared.
+    pub fn insert(&self, ast: &AST, node: &Node<'j, J>) {
+        if let Some(id) = self.ids.get(&(ast as *const AST)) {
+            self.results.borrow_mut().insert(*id, node.clone());
+        }
+    }
+    /// Assigns the same id to nodes with the same shape.
+    ///
+    /// Only nodes whose result solely depends on the current node are
+    /// recorded, _i.e_ compound nodes that do not reference variables
+    /// or placeholders.
+    /// Returns the id of the node, or `None` if its result may depend
+    /// on the variables in scope.
+    fn intern(
+        ast: &AST,
+        shapes: &mut HashMap<Shape, usize>,
+        nodes: &mut Vec<(*const AST, usize)>,
+    ) -> Option<usize> {
+        let children = ast.node_type.children();
+        let mut ids = Vec::with_capacity(children.len());
+        let mut is_pure = true;
+        for child in &children {
+            match Self::intern(child, shapes, nodes) {
+                Some(id) => ids.push(id),
+                None => is_pure = false,
+            }
+        }
+        if !is_pure
+            || matches!(
+                ast.node_type,
+                NodeType::Placeholder(..) | NodeType::VariableRef(..)
+            )
+        {
+            return None;
+        }
+        let text = match &ast.node_type {
+            NodeType::MultiSelectHash(map) => format!("{:?}", map.keys().collect::<Vec<_>>()),
+            node_type if children.is_empty() => format!("{:?}", node_type),
+            _ => String::new(),
+        };
+        let shape = Shape {
+            kind: discriminant(&ast.node_type),
+            text,
+            children: ids,
+        };
+        let count = shapes.len();
+        let id = *shapes.entry(shape).or_insert(count);
+        // evaluating leaves is cheaper than looking up their results
+        let is_cheap = children.is_empty()
+            || matches!(
+                ast.node_type,
+                NodeType::Expression(..) | NodeType::LambdaExpression(..)
+            );
+        if !is_cheap {
+            nodes.push((ast as *const AST, id));
+        }
+        Some(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::Kind;
+    use crate::functions::{
+        DataType, Function,
+        ParamTypes::*,
+        Parameter::{self, *},
+    };
+    use crate::{FunctionContext, FunctionRegistrar, Value, function};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    function!(tick, [value => Required(Of(DataType::Any))], |_: &tick, args: &Vec<Value>, _: &dyn FunctionContext| {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        Ok(args[0].clone())
+    });
+
+    fn parse_all(expressions: &[&str]) -> Vec<AST> {
+        expressions
+            .iter()
+            .map(|x| crate::parse(x).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn it_evaluates_shared_sub_expressions_once() {
+        let mut runtime = Runtime::create_runtime();
+        runtime.register(Box::new(tick::new()));
+        let root = Value::from_json(r#"{"detail": {"user": "ada", "tags": ["a", "b"]}}"#).unwrap();
+        let asts = parse_all(&[
+            "tick(detail).user",
+            "tick(detail).tags[0]",
+            "length(tick(detail).tags)",
+            "tick(detail).tags[?@ == 'b'] | [0]",
+        ]);
+
+        let results = runtime.search_many(&asts, &root);
+        assert_eq!(1, CALLS.load(Ordering::SeqCst));
+
+        let expected = ["\"ada\"", "\"a\"", "2", "\"b\""];
+        for (expected, result) in expected.iter().zip(results) {
+            assert_eq!(Value::from_json(expected).unwrap(), result.unwrap());
+        }
+    }
+
+    #[test]
+    fn it_reports_errors_per_expression() {
+        let runtime = Runtime::get_shared_runtime();
+        let root = Value::from_json(r#"{"foo": {"bar": [1, 2]}}"#).unwrap();
+        let asts = parse_all(&[
+            "foo.bar[0]",
+            "length(foo.bar[0])",
+            "$x",
+            "let $x = foo.bar in $x[1]",
+            "foo.bar[1]",
+        ]);
+
+        let results = runtime.search_many(&asts, &root);
+        assert_eq!(5, results.len());
+        assert_eq!(1, *results[0].as_ref().unwrap());
+        assert_eq!(Kind::InvalidType, results[1].as_ref().unwrap_err().kind);
+        assert_eq!(
+            Kind::UndefinedVariable,
+            results[2].as_ref().unwrap_err().kind
+        );
+        assert_eq!(2, *results[3].as_ref().unwrap());
+        assert_eq!(2, *results[4].as_ref().unwrap());
+    }
+
+    #[test]
+    fn it_matches_expressions_by_shape() {
+        let asts = parse_all(&["a.b.c", "(a.b).d", "{x: a.b}", "{y: a.b}", "[$v.a, $v.a]"]);
+        let shared = SharedResults::<Value>::new(&asts);
+        let mut ids: Vec<&usize> = shared.ids.values().collect();
+        ids.sort();
+        ids.dedup();
+        // `a.b` is shared, whereas `{x: a.b}` and `{y: a.b}` differ by key
+        assert_eq!(1, ids.len());
+        assert_eq!(4, shared.ids.len());
+    }
+}
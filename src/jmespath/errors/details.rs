@@ -0,0 +1,100 @@
+use crate::{Value, functions::DataType};
+
+/// Represents machine-readable details about an [`Error`](super::Error).
+///
+/// Each variant holds the data that is otherwise rendered
+/// in the error message, for the corresponding error [`Kind`](super::Kind).
+///
+/// # Example
+/// ```
+/// use jmespath_community as jmespath;
+/// use jmespath::errors::Details;
+/// use jmespath::{search, Value};
+///
+/// let err = search("length(@, @)", &Value::Null).unwrap_err();
+///
+/// assert!(matches!(
+///     err.details.as_deref(),
+///     Some(Details::InvalidArity { max_expected: Some(1), supplied: 2, .. })
+/// ));
+/// ```
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(untagged)]
+pub enum Details {
+    /// Details for a [`Kind::InvalidArity`](super::Kind::InvalidArity) error.
+    InvalidArity {
+        /// The name of the function.
+        function: String,
+        /// The minimum number of arguments, if too few were supplied.
+        min_expected: Option<usize>,
+        /// The maximum number of arguments, if too many were supplied.
+        max_expected: Option<usize>,
+        /// The number of arguments supplied.
+        supplied: usize,
+        /// Whether the function accepts a variable number of arguments.
+        variadic: bool,
+    },
+    /// Details for a [`Kind::InvalidType`](super::Kind::InvalidType) error.
+    InvalidType {
+        /// The name of the function.
+        function: String,
+        /// The name of the parameter.
+        parameter: String,
+        /// Whether the parameter is an expression parameter.
+        expression_parameter: bool,
+        /// The data types the parameter accepts.
+        expected: Vec<DataType>,
+        /// The value that was received.
+        #[serde(serialize_with = "serialize_received")]
+        received: Value,
+        /// The data type of the value that was received.
+        received_type: DataType,
+    },
+    /// Details for a [`Kind::InvalidValue`](super::Kind::InvalidValue) error.
+    InvalidValue {
+        /// The name of the function.
+        function: String,
+        /// The name of the parameter.
+        parameter: String,
+        /// A description of the values the parameter accepts.
+        expected: String,
+        /// The value that was received, if known.
+        #[serde(serialize_with = "serialize_received_option")]
+        received: Option<Value>,
+    },
+    /// Details for a [`Kind::UndefinedVariable`](super::Kind::UndefinedVariable) error.
+    UndefinedVariable {
+        /// The name of the variable.
+        variable: String,
+    },
+    /// Details for a [`Kind::UnknownFunction`](super::Kind::UnknownFunction) error.
+    UnknownFunction {
+        /// The name of the function.
+        function: String,
+    },
+    /// Details for a [`Kind::Syntax`](super::Kind::Syntax) error.
+    Syntax {
+        /// A description of the tokens the parser expected, if any.
+        expected: Vec<String>,
+    },
+}
+
+// expressions have no JSON representation
+fn serialize_received<S>(value: &Value, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match value {
+        Value::Expression(_) => serializer.serialize_unit(),
+        value => serde::Serialize::serialize(value, serializer),
+    }
+}
+fn serialize_received_option<S>(value: &Option<Value>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match value {
+        Some(value) => serialize_received(value, serializer),
+        None => serializer.serialize_none(),
+    }
+}
@@ -0,0 +1,243 @@
+use super::{Error, Kind, Span};
+use crate::Runtime;
+
+/// Renders an [`Error`] as a human-readable diagnostic message,
+/// showing the offending JMESPath expression with its relevant
+/// range underlined.
+pub(crate) struct Diagnostic<'a> {
+    error: &'a Error,
+    expression: &'a str,
+    help: Option<String>,
+}
+impl<'a> Diagnostic<'a> {
+    /// Creates a new instance of the [`Diagnostic`] type.
+    pub(crate) fn new(error: &'a Error, expression: &'a str) -> Self {
+        Diagnostic {
+            error,
+            expression,
+            help: None,
+        }
+    }
+    /// Suggests the closest function name for an unknown function error.
+    pub(crate) fn suggest_functions<'b, I>(mut self, function_names: I) -> Self
+    where
+        I: IntoIterator<Item = &'b str>,
+    {
+        if self.error.kind != Kind::UnknownFunction {
+            return self;
+        }
+        let span = match self.span() {
+            Some(span) => span,
+            None => return self,
+        };
+        let name: String = self.expression[span.start..]
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        let threshold = std::cmp::max(1, name.chars().count() / 3);
+        let mut candidates: Vec<(usize, &str)> = function_names
+            .into_iter()
+            .map(|candidate| (edit_distance(&name, candidate), candidate))
+            .filter(|(distance, _)| *distance <= threshold)
+            .collect();
+        candidates.sort();
+        self.help = candidates
+            .first()
+            .map(|(_, candidate)| format!("did you mean `{}`?", candidate));
+        self
+    }
+    /// Returns the rendered diagnostic message.
+    pub(crate) fn render(&self) -> String {
+        let mut text = format!("error[{}]: {}", self.error.kind, self.error.message);
+        let span = match self.span() {
+            Some(span) => span,
+            None => return text,
+        };
+
+        let line_start = self.expression[..span.start]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+        let line_end = self.expression[span.start..]
+            .find('\n')
+            .map_or(self.expression.len(), |index| span.start + index);
+        let line_number = self.expression[..line_start].matches('\n').count() + 1;
+        let column = self.expression[line_start..span.start].chars().count();
+        let width = std::cmp::max(
+            1,
+            self.expression[span.start..std::cmp::min(span.end, line_end)]
+                .chars()
+                .count(),
+        );
+
+        let gutter = " ".repeat(line_number.to_string().len());
+        text.push_str(&format!(
+            "\n{gutter}--> {}:{}\n{gutter} |\n{} | {}\n{gutter} | {}{} {}",
+            line_number,
+            column + 1,
+            line_number,
+            &self.expression[line_start..line_end],
+            " ".repeat(column),
+            "^".repeat(width),
+            label(self.error.kind),
+        ));
+        if let Some(help) = &self.help {
+            text.push_str(&format!("\n{gutter} |\n{gutter} = help: {}", help));
+        }
+        text
+    }
+    /// Returns the range of the expression to underline, if known.
+    fn span(&self) -> Option<Span> {
+        let span = match (self.error.span, self.error.position) {
+            (Some(span), _) if !span.is_empty() => span,
+            (_, Some(position)) if position.line > 0 => {
+                let start = self
+                    .expression
+                    .split_inclusive('\n')
+                    .take(position.line - 1)
+                    .map(|line| line.len())
+                    .sum::<usize>()
+                    + self
+                        .expression
+                        .lines()
+                        .nth(position.line - 1)?
+                        .chars()
+                        .take(position.column.saturating_sub(1))
+                        .map(|c| c.len_utf8())
+                        .sum::<usize>();
+                let end = std::cmp::min(start + 1, self.expression.len());
+                Span::new(start, end, position.line, position.column)
+            }
+            _ => return None,
+        };
+        let valid = |offset: usize| {
+            offset <= self.expression.len() && self.expression.is_char_boundary(offset)
+        };
+        if valid(span.start) && valid(span.end) {
+            Some(span)
+        } else {
+            None
+        }
+    }
+}
+
+impl Error {
+    /// Renders this [`Error`] as a human-readable diagnostic message.
+    ///
+    /// The message shows the line of the JMESPath expression
+    /// where the error occurred, with the offending range underlined.
+    ///
+    /// Use [`Runtime::render_error`] to get hints that take
+    /// custom functions into account.
+    ///
+    /// # Example
+    /// ```
+    /// use jmespath_community as jmespath;
+    /// use jmespath::{search, Value};
+    ///
+    /// let expression = "foo | starts_wth(@, 'b')";
+    /// let err = search(expression, &Value::Null).unwrap_err();
+    ///
+    /// let expected = r#"error[unknown-function]: the function 'starts_wth' does not exist
+    ///  --> 1:7
+    ///   |
+    /// 1 | foo | starts_wth(@, 'b')
+    ///   |       ^^^^^^^^^^^^^^^^^^ unknown function
+    ///   |
+    ///   = help: did you mean `starts_with`?"#;
+    /// assert_eq!(expected, err.render(expression));
+    /// ```
+    pub fn render(&self, expression: &str) -> String {
+        Runtime::get_shared_runtime().render_error(self, expression)
+    }
+}
+
+/// Returns a short label that describes an error [`Kind`].
+fn label(kind: Kind) -> &'static str {
+    match kind {
+        Kind::InvalidArity => "wrong number of arguments",
+        Kind::InvalidType => "invalid type",
+        Kind::InvalidValue => "invalid value",
+        Kind::NotANumber => "not a number",
+        Kind::UndefinedVariable => "undefined variable",
+        Kind::UnknownFunction => "unknown function",
+        Kind::Syntax => "syntax error",
+    }
+}
+
+/// Computes the Levenshtein distance between two strings.
+fn edit_distance(left: &str, right: &str) -> usize {
+    let right: Vec<char> = right.chars().collect();
+    let mut distances: Vec<usize> = (0..=right.len()).collect();
+    for (i, l) in left.chars().enumerate() {
+        let mut previous = distances[0];
+        distances[0] = i + 1;
+        for (j, r) in right.iter().enumerate() {
+            let current = distances[j + 1];
+            distances[j + 1] = if l == *r {
+                previous
+            } else {
+                1 + std::cmp::min(previous, std::cmp::min(distances[j], current))
+            };
+            previous = current;
+        }
+    }
+    distances[right.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::Position;
+    use rstest::*;
+
+    #[rstest]
+    #[case(0, "length", "length")]
+    #[case(1, "starts_wth", "starts_with")]
+    #[case(2, "lenght", "length")]
+    #[case(3, "abs", "")]
+    fn edit_distance(#[case] expected: usize, #[case] left: &str, #[case] right: &str) {
+        assert_eq!(expected, super::edit_distance(left, right));
+    }
+
+    #[test]
+    fn render_without_position() {
+        let err = Error::new(Kind::Syntax, "reason");
+        assert_eq!(
+            "error[syntax]: reason",
+            Diagnostic::new(&err, "foo").render()
+        );
+    }
+
+    #[test]
+    fn render_position() {
+        let err = Error::new_at(Kind::Syntax, "reason", Position::new(2, 3));
+        let expected = "error[syntax]: reason\n --> 2:3\n  |\n2 | 'é'.bar\n  |   ^ syntax error";
+        assert_eq!(expected, Diagnostic::new(&err, "foo |\n'é'.bar").render());
+    }
+
+    #[test]
+    fn render_end_of_expression() {
+        let expression = "foo |";
+        let err = crate::parse(expression).unwrap_err();
+        assert!(
+            err.render(expression)
+                .ends_with("\n1 | foo |\n  |      ^ syntax error")
+        );
+    }
+
+    #[test]
+    fn render_span() {
+        let expression = "foo | unknown(@)";
+        let err = crate::search(expression, &crate::Value::Null).unwrap_err();
+        let expected = "error[unknown-function]: the function 'unknown' does not exist\n --> 1:7\n  |\n1 | foo | unknown(@)\n  |       ^^^^^^^^^^ unknown function";
+        assert_eq!(expected, err.render(expression));
+    }
+
+    #[test]
+    fn render_multiline_span() {
+        let expression = "{\n  foo: `1`,\n  bar: length(@)\n} | foo + bar";
+        let err = crate::search(expression, &crate::Value::Null).unwrap_err();
+        let expected = "error[invalid-type]: while calling function 'length', the parameter '$subject' is expected to be either one of [array[any], object, string] but the value 'null' (of type null) was received instead\n --> 3:8\n  |\n3 |   bar: length(@)\n  |        ^^^^^^^^^ invalid type";
+        assert_eq!(expected, err.render(expression));
+    }
+}
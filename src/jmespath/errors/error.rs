@@ -1,7 +1,9 @@
 use std::cmp::Ordering;
 
+use crate::errors::Details;
 use crate::errors::Kind;
 use crate::errors::Position;
+use crate::errors::Span;
 use crate::errors::invalid_arity::InvalidArityErrorBuilderFactory;
 use crate::errors::undefined_variable::UndefinedVariableErrorBuilderFactory;
 use crate::errors::unknown_function::UnknownFunctionErrorBuilderFactory;
@@ -12,7 +14,9 @@ use super::error_builder::InvalidArityErrorBuilder;
 use super::error_builder::UndefinedVariableErrorBuilder;
 
 /// The error type for this crate.
-#[derive(Debug, Clone)]
+///
+/// Errors can be serialized, _e.g_ to JSON, including their [`Details`].
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct Error {
     /// One of the valid error [`Kind`] values.
     pub kind: Kind,
@@ -20,6 +24,10 @@ pub struct Error {
     pub message: String,
     /// The position within a JMESPath expression.
     pub position: Option<Position>,
+    /// The range of the JMESPath expression the error relates to, if known.
+    pub span: Option<Span>,
+    /// Machine-readable details about the error, if available.
+    pub details: Option<Box<Details>>,
 }
 impl Error {
     /// Creates a new instance of the [`Error`] type.
@@ -28,6 +36,8 @@ impl Error {
             kind,
             message: message.to_string(),
             position: None,
+            span: None,
+            details: None,
         }
     }
     /// Creates a new instance of the [`Error`] type with specified [`Position`].
@@ -37,6 +47,8 @@ impl Error {
             kind,
             message: message.to_string(),
             position: Some(position),
+            span: None,
+            details: None,
         }
     }
     /// Raises a runtime error when not enough arguments are supplied to a JMESPath [Function](crate::functions::Function).
@@ -194,6 +206,25 @@ mod tests {
         assert_eq!(expected, left.partial_cmp(&right).unwrap());
     }
 
+    #[test]
+    fn it_serializes_to_json() {
+        let err = crate::search("length(`1`)", &crate::Value::Null).unwrap_err();
+        let expected = r#"{"kind":"invalid-type","message":"while calling function 'length', the parameter '$subject' is expected to be either one of [array[any], object, string] but the value '1' (of type number) was received instead","position":{"line":1,"column":1},"span":{"start":0,"end":11,"line":1,"column":1},"details":{"function":"length","parameter":"subject","expression_parameter":false,"expected":["array[any]","object","string"],"received":1,"received_type":"number"}}"#;
+        assert_eq!(expected, serde_json::to_string(&err).unwrap());
+    }
+
+    #[rstest]
+    #[case(r#"{"function":"unknown"}"#, Error::unknown_function("unknown"))]
+    #[case(r#"{"variable":"foo"}"#, Error::undefined_variable("foo"))]
+    #[case(
+        r#"{"function":"sum","min_expected":2,"max_expected":null,"supplied":1,"variadic":true}"#,
+        Error::too_few_arguments("sum", 2, 1, true)
+    )]
+    #[case(r#"{"expected":["`:`","`]`"]}"#, crate::parse("[1").unwrap_err())]
+    fn it_serializes_details(#[case] expected: &str, #[case] error: Error) {
+        assert_eq!(expected, serde_json::to_string(&error.details).unwrap());
+    }
+
     //    use crate::{
     //        errors::{
     //            error_builder::{ErrorBuilder, FunctionErrorBuilder, InvalidTypeErrorBuilder},
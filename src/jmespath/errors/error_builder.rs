@@ -1,9 +1,10 @@
 use crate::{Value, functions::DataType};
 
-use super::{Error, Kind, Position};
+use super::{Error, Kind, Position, Span};
 
 pub(crate) trait ErrorBuilder {
     fn at(&mut self, position: Position) -> &mut Self;
+    fn at_span(&mut self, span: Span) -> &mut Self;
     fn build(&mut self) -> Error;
 }
 pub(crate) trait FunctionErrorBuilder: ErrorBuilder {
@@ -33,6 +34,7 @@ pub(crate) trait NotANumberErrorBuilder: ErrorBuilder {
 pub(crate) trait SyntaxErrorBuilder: ErrorBuilder {
     fn set_kind(&mut self, kind: Kind) -> &mut Self;
     fn for_reason(&mut self, reason: &str) -> &mut Self;
+    fn expecting(&mut self, tokens: &[&str]) -> &mut Self;
 }
 pub(crate) trait UndefinedVariableErrorBuilder: ErrorBuilder {
     fn for_variable(&mut self, name: &str) -> &mut Self;
@@ -1,4 +1,4 @@
-use super::{Error, Kind, Position, error_builder};
+use super::{Details, Error, Kind, Position, Span, error_builder};
 
 pub(crate) trait InvalidArityErrorBuilderFactory {
     type Builder: super::error_builder::InvalidArityErrorBuilder;
@@ -19,12 +19,14 @@ pub(crate) struct InvalidArityErrorBuilder {
     message: String,
     min_count: Option<usize>,
     position: Option<Position>,
+    span: Option<Span>,
 }
 impl InvalidArityErrorBuilder {
     pub fn new() -> Self {
         InvalidArityErrorBuilder {
             message: "".to_string(),
             position: None,
+            span: None,
 
             function_name: "".to_string(),
             count: 0,
@@ -69,12 +71,25 @@ impl error_builder::ErrorBuilder for InvalidArityErrorBuilder {
         self.position = Some(position);
         self
     }
+    fn at_span(&mut self, span: Span) -> &mut Self {
+        self.position = Some(span.position());
+        self.span = Some(span);
+        self
+    }
     fn build(&mut self) -> Error {
         self.format();
         return Error {
             kind: Kind::InvalidArity,
             message: std::mem::replace(&mut self.message, String::new()),
             position: self.position,
+            span: self.span,
+            details: Some(Box::new(Details::InvalidArity {
+                function: self.function_name.clone(),
+                min_expected: self.min_count,
+                max_expected: self.max_count,
+                supplied: self.count,
+                variadic: self.is_variadic,
+            })),
         };
     }
 }
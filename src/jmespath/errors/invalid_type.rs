@@ -1,6 +1,6 @@
 use crate::{Value, functions::DataType};
 
-use super::{Error, Kind, Position, error_builder};
+use super::{Details, Error, Kind, Position, Span, error_builder};
 
 pub(crate) trait InvalidTypeErrorBuilderFactory {
     type Builder: super::error_builder::InvalidTypeErrorBuilder;
@@ -20,6 +20,7 @@ pub(crate) struct InvalidTypeErrorBuilder {
     function_name: String,
     parameter_name: String,
     position: Option<Position>,
+    span: Option<Span>,
     received_value: Value,
     received_data_type: DataType,
     is_expref: bool,
@@ -29,6 +30,7 @@ impl InvalidTypeErrorBuilder {
         InvalidTypeErrorBuilder {
             message: "".to_string(),
             position: None,
+            span: None,
 
             function_name: "".to_string(),
             parameter_name: "".to_string(),
@@ -80,6 +82,14 @@ impl InvalidTypeErrorBuilder {
         } else {
             format!("either one of [{}]", data_types)
         };
+        if self.function_name.is_empty() {
+            // the value is not a function argument, _e.g_ an index
+            self.message = format!(
+                "the {} is expected to be {} but the value '{}' (of type {}) was received instead",
+                self.parameter_name, data_types_list, self.received_value, self.received_data_type
+            );
+            return;
+        }
         self.message = format!(
             "while calling function '{}', the parameter '${}' is expected to be {} but the value '{}' (of type {}) was received instead",
             self.function_name,
@@ -101,12 +111,26 @@ impl error_builder::ErrorBuilder for InvalidTypeErrorBuilder {
         self.position = Some(position);
         self
     }
+    fn at_span(&mut self, span: Span) -> &mut Self {
+        self.position = Some(span.position());
+        self.span = Some(span);
+        self
+    }
     fn build(&mut self) -> Error {
         self.format();
         return Error {
             kind: Kind::InvalidType,
             message: std::mem::replace(&mut self.message, String::new()),
             position: self.position,
+            span: self.span,
+            details: Some(Box::new(Details::InvalidType {
+                function: self.function_name.clone(),
+                parameter: self.parameter_name.clone(),
+                expression_parameter: self.is_expref,
+                expected: self.expected_data_types.clone(),
+                received: self.received_value.clone(),
+                received_type: self.received_data_type,
+            })),
         };
     }
 }
@@ -157,6 +181,20 @@ mod tests {
         );
     }
     #[test]
+    fn invalid_type_outside_function() {
+        let err = Error::get_invalid_type_error_builder()
+            .at(Position::new(1, 4))
+            .for_parameter("index")
+            .expected_data_types(&vec![DataType::Number])
+            .received(&Value::String("a".to_string()))
+            .build();
+
+        assert_eq!(
+            "Error(1, 4): invalid-type, the index is expected to be number but the value '\"a\"' (of type string) was received instead",
+            format!("{}", err)
+        );
+    }
+    #[test]
     fn invalid_type_any() {
         let err = Error::get_invalid_type_error_builder()
             .at(Position::new(1, 4))
@@ -1,6 +1,6 @@
 use crate::Value;
 
-use super::{Error, Kind, Position, error_builder};
+use super::{Details, Error, Kind, Position, Span, error_builder};
 
 pub(crate) trait InvalidValueErrorBuilderFactory {
     type Builder: super::error_builder::InvalidValueErrorBuilder;
@@ -20,6 +20,7 @@ pub(crate) struct InvalidValueErrorBuilder {
     message: String,
     parameter_name: String,
     position: Option<Position>,
+    span: Option<Span>,
     received_value: Option<Value>,
 }
 impl InvalidValueErrorBuilder {
@@ -30,10 +31,23 @@ impl InvalidValueErrorBuilder {
             message: "".to_string(),
             parameter_name: "".to_string(),
             position: None,
+            span: None,
             received_value: None,
         }
     }
     fn format(&mut self) {
+        if self.function_name.is_empty() {
+            // the value is not a function argument, _e.g_ an index
+            let received = match &self.received_value {
+                Some(value) => format!("'{}' (of type {})", value, value.get_data_type()),
+                None => "an invalid value".to_string(),
+            };
+            self.message = format!(
+                "the {} evaluated to {}: expected {} instead",
+                self.parameter_name, received, self.expected
+            );
+            return;
+        }
         if let Some(value) = &self.received_value {
             let data_type = value.get_data_type();
             self.message = format!(
@@ -59,12 +73,24 @@ impl error_builder::ErrorBuilder for InvalidValueErrorBuilder {
         self.position = Some(position);
         self
     }
+    fn at_span(&mut self, span: Span) -> &mut Self {
+        self.position = Some(span.position());
+        self.span = Some(span);
+        self
+    }
     fn build(&mut self) -> Error {
         self.format();
         return Error {
             kind: Kind::InvalidValue,
             message: std::mem::replace(&mut self.message, String::new()),
             position: self.position,
+            span: self.span,
+            details: Some(Box::new(Details::InvalidValue {
+                function: self.function_name.clone(),
+                parameter: self.parameter_name.clone(),
+                expected: self.expected.clone(),
+                received: self.received_value.clone(),
+            })),
         };
     }
 }
@@ -106,6 +132,20 @@ mod tests {
         );
     }
     #[test]
+    fn invalid_value_outside_function() {
+        let err = Error::get_invalid_value_error_builder()
+            .at(Position::new(1, 4))
+            .for_parameter("index")
+            .received(&Value::from_f64(1.5).unwrap())
+            .expected("an integer")
+            .build();
+
+        assert_eq!(
+            "Error(1, 4): invalid-value, the index evaluated to '1.5' (of type number): expected an integer instead",
+            format!("{}", err)
+        );
+    }
+    #[test]
     fn invalid_value_received_value() {
         let err = Error::get_invalid_value_error_builder()
             .at(Position::new(1, 4))
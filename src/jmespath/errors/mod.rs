@@ -1,7 +1,10 @@
+mod details;
+pub(crate) mod diagnostic;
 mod error;
 mod kind;
 mod position;
-mod santiago;
+pub(crate) mod santiago;
+mod span;
 
 pub(crate) mod error_builder;
 pub(crate) mod invalid_arity;
@@ -12,6 +15,8 @@ pub(crate) mod syntax;
 pub(crate) mod undefined_variable;
 pub(crate) mod unknown_function;
 
+pub use details::Details;
 pub use error::Error;
 pub use kind::Kind;
 pub use position::Position;
+pub use span::Span;
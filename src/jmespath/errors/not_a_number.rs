@@ -1,4 +1,4 @@
-use super::{Error, Kind, Position, error_builder};
+use super::{Error, Kind, Position, Span, error_builder};
 
 pub(crate) trait NotANumberErrorBuilderFactory {
     type Builder: super::error_builder::NotANumberErrorBuilder;
@@ -14,12 +14,14 @@ impl self::NotANumberErrorBuilderFactory for Error {
 pub(crate) struct NotANumberErrorBuilder {
     message: String,
     position: Option<Position>,
+    span: Option<Span>,
 }
 impl NotANumberErrorBuilder {
     pub fn new() -> Self {
         NotANumberErrorBuilder {
             message: "".to_string(),
             position: None,
+            span: None,
         }
     }
 }
@@ -34,11 +36,18 @@ impl error_builder::ErrorBuilder for NotANumberErrorBuilder {
         self.position = Some(position);
         self
     }
+    fn at_span(&mut self, span: Span) -> &mut Self {
+        self.position = Some(span.position());
+        self.span = Some(span);
+        self
+    }
     fn build(&mut self) -> Error {
         return Error {
             kind: Kind::NotANumber,
             message: std::mem::replace(&mut self.message, String::new()),
             position: self.position,
+            span: self.span,
+            details: None,
         };
     }
 }
@@ -1,5 +1,5 @@
 /// Represents a location within a JMESPath expression.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, serde::Serialize)]
 pub struct Position {
     /// The line number, starting at line 1.
     pub line: usize,
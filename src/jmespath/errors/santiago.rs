@@ -1,21 +1,248 @@
-use super::{Kind, Position};
-use crate::{Error, parser::AST};
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use santiago::grammar::Grammar;
+use santiago::lexer::Lexeme;
+use santiago::parser::ParseError;
+
+use super::Position;
+use super::error_builder::{ErrorBuilder, SyntaxErrorBuilder};
+use super::syntax::SyntaxErrorBuilderFactory;
+use crate::parser::grammar::grammar;
+use crate::parser::{AST, MISSING};
+use crate::{Error, lexer::SourceMap};
 
 impl std::error::Error for Error {}
 
-impl From<santiago::parser::ParseError<AST>> for Error {
-    fn from(error: santiago::parser::ParseError<AST>) -> Self {
-        let kind = Kind::Syntax;
-        // TODO: better error handling
-        let message = format!("{:?}", error);
-        let position = match &error.at {
-            Some(lexeme) => Some(Position::new(lexeme.position.line, lexeme.position.column)),
-            None => Some(Position::new(1, 1)),
+/// Returns the index of the token that could not be parsed.
+///
+/// The returned index equals the number of tokens when
+/// the parser reached the end of the expression.
+pub(crate) fn failing_index(error: &ParseError<AST>, tokens: &[Rc<Lexeme>]) -> usize {
+    // santiago reports the last token that was successfully parsed
+    match &error.at {
+        Some(at) => tokens
+            .iter()
+            .position(|token| Rc::ptr_eq(token, at))
+            .map_or(tokens.len(), |index| index + 1),
+        None => 0,
+    }
+}
+
+/// Raises a syntax error that describes the unexpected token
+/// as well as the tokens the parser expected instead.
+pub(crate) fn syntax_error(
+    error: &ParseError<AST>,
+    tokens: &[Rc<Lexeme>],
+    source_map: &SourceMap,
+    grammar: &Grammar<AST>,
+) -> Error {
+    let index = failing_index(error, tokens);
+    let (found, span) = match tokens.get(index) {
+        Some(token) => (
+            format!("unexpected {}", describe(&token.kind)),
+            source_map.span(token),
+        ),
+        None => ("unexpected end of expression".to_string(), source_map.end()),
+    };
+    unexpected(error, grammar, found).at_span(span).build()
+}
+
+/// Describes the unexpected token along with the tokens the parser expected instead.
+fn unexpected(
+    error: &ParseError<AST>,
+    grammar: &Grammar<AST>,
+    found: String,
+) -> <Error as SyntaxErrorBuilderFactory>::Builder {
+    let expected = expected_tokens(error, grammar);
+    let reason = match expected.len() {
+        0 => found,
+        1 => format!("{}, expected {}", found, expected[0]),
+        _ => format!("{}, expected one of {}", found, expected.join(", ")),
+    };
+    let mut builder = Error::get_syntax_error_builder();
+    builder.for_reason(&reason).expecting(&expected);
+    builder
+}
+
+impl From<ParseError<AST>> for Error {
+    fn from(error: ParseError<AST>) -> Self {
+        // the tokens are not available, so the error is located
+        // at the last token that was successfully parsed
+        let grammar = grammar(&Rc::new(SourceMap::new("")));
+        let (found, position) = match &error.at {
+            Some(at) => (
+                format!("unexpected token after {}", describe(&at.kind)),
+                Position::new(at.position.line, at.position.column),
+            ),
+            None => ("unexpected token".to_string(), Position::new(1, 1)),
+        };
+        unexpected(&error, &grammar, found).at(position).build()
+    }
+}
+
+/// Returns a human-readable description of the tokens
+/// that would have allowed the parser to make progress.
+fn expected_tokens(error: &ParseError<AST>, grammar: &Grammar<AST>) -> Vec<&'static str> {
+    // santiago does not expose parser states,
+    // so the expected symbols are extracted from their textual representation
+    // _e.g_ `"expression" := rules "expression" "dot" • "sub_expression_rhs" [0-2]`
+    let mut kinds: HashSet<String> = HashSet::new();
+    for state in error.states.iter() {
+        let text = state.to_string();
+        let Some((_, production)) = text.split_once(" := ") else {
+            continue;
+        };
+        let Some(symbol) = production
+            .split_once('•')
+            .and_then(|(_, next)| quoted(next))
+        else {
+            continue;
+        };
+        if production.starts_with("lexemes ") {
+            kinds.insert(symbol.to_string());
+        } else {
+            first_lexemes(grammar, symbol, &mut HashSet::new(), &mut kinds);
+        }
+    }
+    let mut expected: Vec<&'static str> = kinds
+        .iter()
+        .filter(|kind| kind.as_str() != MISSING)
+        .map(|kind| describe(kind))
+        .collect();
+    // list punctuation first
+    expected.sort_by_key(|text| (!text.starts_with('`'), *text));
+    expected.dedup();
+    expected
+}
+
+/// Collects the kinds of the lexemes that may start the specified grammar rule.
+fn first_lexemes(
+    grammar: &Grammar<AST>,
+    rule: &str,
+    visited: &mut HashSet<String>,
+    kinds: &mut HashSet<String>,
+) {
+    let name = rule.to_string();
+    let Some(rule) = grammar.rules.get(&name) else {
+        return;
+    };
+    if !visited.insert(name) {
+        return;
+    }
+    // _e.g_ `"dot" := lexemes "dot"\n    | rules "expression" "dot"`
+    let text = rule.to_string();
+    let Some((_, productions)) = text.split_once(" := ") else {
+        return;
+    };
+    for production in productions.split("\n    | ") {
+        let Some(symbol) = quoted(production) else {
+            continue;
         };
-        Self {
-            kind,
-            message,
-            position,
+        if production.starts_with("lexemes ") {
+            kinds.insert(symbol.to_string());
+        } else {
+            first_lexemes(grammar, symbol, visited, kinds);
         }
     }
 }
+
+/// Returns the first double-quoted symbol in the specified text.
+fn quoted(text: &str) -> Option<&str> {
+    let (_, rest) = text.split_once('"')?;
+    let (symbol, _) = rest.split_once('"')?;
+    Some(symbol)
+}
+
+/// Returns a human-readable description of a lexeme kind.
+fn describe(kind: &str) -> &'static str {
+    match kind {
+        "and" => "`&&`",
+        "arrow" => "`=>`",
+        "assign" => "`=`",
+        "colon" => "`:`",
+        "comma" => "`,`",
+        "current" => "`@`",
+        "descendant" => "`..`",
+        "div" => "`//`",
+        "divide" => "`/`",
+        "dot" => "`.`",
+        "equal" => "`==`",
+        "expref" => "`&`",
+        "filter" => "`[?`",
+        "flatten" => "`[]`",
+        "greater_than" => "`>`",
+        "greater_than_or_equal" => "`>=`",
+        "in" => "`in`",
+        "json_value" => "JSON literal",
+        "lbrace" => "`{`",
+        "lbracket" => "`[`",
+        "less_than" => "`<`",
+        "less_than_or_equal" => "`<=`",
+        "let" => "`let`",
+        "let_star" => "`let*`",
+        "lparen" => "`(`",
+        "minus" => "`-`",
+        "mod" => "`%`",
+        "multiply" => "`×`",
+        "not" => "`!`",
+        "not_equal" => "`!=`",
+        "number" => "number",
+        "or" => "`||`",
+        "pipe" => "`|`",
+        "plus" => "`+`",
+        "question" => "`?`",
+        "quoted_string" => "quoted identifier",
+        "raw_string" => "raw string",
+        "rbrace" => "`}`",
+        "rbracket" => "`]`",
+        "root" => "`$`",
+        "rparen" => "`)`",
+        "star" => "`*`",
+        "unquoted_string" => "identifier",
+        "variable_ref" => "variable",
+        _ => "token",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::{Kind, Position};
+    use crate::parser::parse;
+    use rstest::*;
+
+    #[rstest]
+    #[case(
+        "unexpected `@`, expected one of `*`, `[`, `{`, identifier, quoted identifier",
+        (1, 5),
+        "foo.@"
+    )]
+    #[case("unexpected `}`, expected one of `:`, `]`", (1, 6), "foo[0}")]
+    #[case(
+        "unexpected end of expression, expected one of `!`, `$`, `(`, `*`, `+`, `-`, `..`, `:`, `@`, `[?`, `[]`, `[`, `let*`, `let`, `{`, JSON literal, identifier, quoted identifier, raw string, variable",
+        (1, 6),
+        "foo |"
+    )]
+    fn syntax_error(#[case] message: &str, #[case] position: (usize, usize), #[case] input: &str) {
+        let err = parse(input).unwrap_err();
+        assert_eq!(Kind::Syntax, err.kind);
+        assert_eq!(message, err.message);
+        assert_eq!(Some(Position::new(position.0, position.1)), err.position);
+    }
+
+    #[test]
+    fn it_converts_parse_errors() {
+        let tokens = crate::lexer::tokenize("foo.@").unwrap();
+        let source_map = std::rc::Rc::new(crate::lexer::SourceMap::new("foo.@"));
+        let grammar = crate::parser::grammar::grammar(&source_map);
+        let error = santiago::parser::parse(&grammar, &tokens).unwrap_err();
+
+        let err = crate::Error::from(error);
+        assert_eq!(Kind::Syntax, err.kind);
+        assert_eq!(
+            "unexpected token after `.`, expected one of `*`, `[`, `{`, identifier, quoted identifier",
+            err.message
+        );
+        assert_eq!(Some(Position::new(1, 4)), err.position);
+    }
+}
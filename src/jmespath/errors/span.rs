@@ -0,0 +1,109 @@
+use super::Position;
+
+/// Represents a range of bytes within a JMESPath expression.
+///
+/// A [`Span`] covers the `start..end` byte offsets of a node in the
+/// source expression, as well as the line and column of its anchor,
+/// _i.e_ the [`Position`] that is reported for the node.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, serde::Serialize)]
+pub struct Span {
+    /// The byte offset of the first character, inclusive.
+    pub start: usize,
+    /// The byte offset of the last character, exclusive.
+    pub end: usize,
+    /// The line number, starting at line 1.
+    pub line: usize,
+    /// The column number, starting at column 1.
+    pub column: usize,
+}
+impl Span {
+    /// A default invalid 'null-object' [`Span`].
+    pub(crate) fn default() -> Self {
+        Self::new(0, 0, 0, 0)
+    }
+    /// Creates a new instance of the [`Span`] type.
+    pub(crate) fn new(start: usize, end: usize, line: usize, column: usize) -> Self {
+        Span {
+            start,
+            end,
+            line,
+            column,
+        }
+    }
+    /// Returns the line and column of this [`Span`].
+    pub fn position(&self) -> Position {
+        Position::new(self.line, self.column)
+    }
+    /// Returns the length of this [`Span`] in bytes.
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+    /// Returns `true` if this [`Span`] does not cover any byte.
+    ///
+    /// This is the case for nodes that have been created
+    /// programmatically, using only a [`Position`].
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+    /// Returns a [`Span`] that covers both this span and `other`.
+    ///
+    /// The resulting span keeps the line and column of this span.
+    pub(crate) fn union(&self, other: &Span) -> Span {
+        if other.is_empty() {
+            return *self;
+        }
+        if self.is_empty() {
+            return Span::new(other.start, other.end, self.line, self.column);
+        }
+        Span::new(
+            std::cmp::min(self.start, other.start),
+            std::cmp::max(self.end, other.end),
+            self.line,
+            self.column,
+        )
+    }
+}
+
+impl From<Position> for Span {
+    fn from(position: Position) -> Self {
+        Span::new(0, 0, position.line, position.column)
+    }
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "({}, {})[{}..{}]",
+            self.line, self.column, self.start, self.end
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case("(1, 5)[4..7]", Span::new(4, 7, 1, 5))]
+    fn it_implements_display_trait(#[case] expected: &str, #[case] span: Span) {
+        assert_eq!(expected, format!("{}", span));
+    }
+
+    #[test]
+    fn it_converts_from_position() {
+        let span: Span = Position::new(1, 3).into();
+        assert!(span.is_empty());
+        assert_eq!(Position::new(1, 3), span.position());
+    }
+
+    #[rstest]
+    #[case(Span::new(0, 5, 1, 5), Span::new(4, 5, 1, 5), Span::new(0, 3, 1, 1))]
+    #[case(Span::new(4, 9, 1, 5), Span::new(4, 5, 1, 5), Span::new(6, 9, 1, 7))]
+    #[case(Span::new(4, 5, 1, 5), Span::new(4, 5, 1, 5), Span::default())]
+    #[case(Span::new(6, 9, 1, 5), Span::new(0, 0, 1, 5), Span::new(6, 9, 1, 7))]
+    fn union(#[case] expected: Span, #[case] left: Span, #[case] right: Span) {
+        assert_eq!(expected, left.union(&right));
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Error, Kind, Position, error_builder};
+use super::{Details, Error, Kind, Position, Span, error_builder};
 
 pub(crate) trait SyntaxErrorBuilderFactory {
     type Builder: super::error_builder::SyntaxErrorBuilder;
@@ -15,7 +15,9 @@ pub struct SyntaxErrorBuilder {
     kind: Kind,
     message: String,
     position: Option<Position>,
+    span: Option<Span>,
     reason: String,
+    expected: Vec<String>,
 }
 impl SyntaxErrorBuilder {
     pub fn new() -> Self {
@@ -24,6 +26,8 @@ impl SyntaxErrorBuilder {
             message: "".to_string(),
             reason: "".to_string(),
             position: None,
+            span: None,
+            expected: Vec::new(),
         }
     }
     fn format(&mut self) {
@@ -39,18 +43,36 @@ impl error_builder::SyntaxErrorBuilder for SyntaxErrorBuilder {
         self.reason = reason.to_string();
         self
     }
+    fn expecting(&mut self, tokens: &[&str]) -> &mut Self {
+        self.expected = tokens.iter().map(|token| token.to_string()).collect();
+        self
+    }
 }
 impl error_builder::ErrorBuilder for SyntaxErrorBuilder {
     fn at(&mut self, position: Position) -> &mut Self {
         self.position = Some(position);
         self
     }
+    fn at_span(&mut self, span: Span) -> &mut Self {
+        self.position = Some(span.position());
+        self.span = Some(span);
+        self
+    }
     fn build(&mut self) -> Error {
         self.format();
+        // this builder is also used to raise errors of other kinds
+        let details = match self.kind {
+            Kind::Syntax => Some(Box::new(Details::Syntax {
+                expected: std::mem::take(&mut self.expected),
+            })),
+            _ => None,
+        };
         return Error {
             kind: self.kind,
             message: std::mem::replace(&mut self.message, String::new()),
             position: self.position,
+            span: self.span,
+            details,
         };
     }
 }
@@ -1,4 +1,4 @@
-use super::{Error, Kind, Position, error_builder};
+use super::{Details, Error, Kind, Position, Span, error_builder};
 
 pub(crate) trait UndefinedVariableErrorBuilderFactory {
     type Builder: super::error_builder::UndefinedVariableErrorBuilder;
@@ -14,6 +14,7 @@ impl self::UndefinedVariableErrorBuilderFactory for Error {
 pub(crate) struct UndefinedVariableErrorBuilder {
     message: String,
     position: Option<Position>,
+    span: Option<Span>,
     variable_name: String,
 }
 impl UndefinedVariableErrorBuilder {
@@ -21,6 +22,7 @@ impl UndefinedVariableErrorBuilder {
         UndefinedVariableErrorBuilder {
             message: "".to_string(),
             position: None,
+            span: None,
 
             variable_name: "".to_string(),
         }
@@ -40,12 +42,21 @@ impl error_builder::ErrorBuilder for UndefinedVariableErrorBuilder {
         self.position = Some(position);
         self
     }
+    fn at_span(&mut self, span: Span) -> &mut Self {
+        self.position = Some(span.position());
+        self.span = Some(span);
+        self
+    }
     fn build(&mut self) -> Error {
         self.format();
         return Error {
             kind: Kind::UndefinedVariable,
             message: std::mem::replace(&mut self.message, String::new()),
             position: self.position,
+            span: self.span,
+            details: Some(Box::new(Details::UndefinedVariable {
+                variable: self.variable_name.clone(),
+            })),
         };
     }
 }
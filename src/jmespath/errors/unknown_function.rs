@@ -1,4 +1,4 @@
-use super::{Error, Kind, Position, error_builder};
+use super::{Details, Error, Kind, Position, Span, error_builder};
 
 pub(crate) trait UnknownFunctionErrorBuilderFactory {
     type Builder: super::error_builder::UnknownFunctionErrorBuilder;
@@ -15,12 +15,14 @@ pub(crate) struct UnknownFunctionErrorBuilder {
     function_name: String,
     message: String,
     position: Option<Position>,
+    span: Option<Span>,
 }
 impl UnknownFunctionErrorBuilder {
     pub fn new() -> Self {
         UnknownFunctionErrorBuilder {
             message: "".to_string(),
             position: None,
+            span: None,
 
             function_name: "".to_string(),
         }
@@ -41,12 +43,21 @@ impl error_builder::ErrorBuilder for UnknownFunctionErrorBuilder {
         self.position = Some(position);
         self
     }
+    fn at_span(&mut self, span: Span) -> &mut Self {
+        self.position = Some(span.position());
+        self.span = Some(span);
+        self
+    }
     fn build(&mut self) -> Error {
         self.format();
         return Error {
             kind: Kind::UnknownFunction,
             message: std::mem::replace(&mut self.message, String::new()),
             position: self.position,
+            span: self.span,
+            details: Some(Box::new(Details::UnknownFunction {
+                function: self.function_name.clone(),
+            })),
         };
     }
 }
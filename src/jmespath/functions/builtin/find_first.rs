@@ -35,7 +35,7 @@ function!(find_first, [
         let start = if args.len() > 2 { args[2].as_number().unwrap() } else { zero };
         let end = if args.len() > 3 { args[3].as_number().unwrap() } else { infinite };
 
-        if !Self::is_integer(start.number) {
+        if !Self::is_integer(start.as_f64()) {
             return Err(RuntimeError::get_invalid_value_error_builder()
             .for_function(me.get_name())
             .for_parameter("start")
@@ -43,7 +43,7 @@ function!(find_first, [
             .received(&args[2])
             .build());
         }
-        if !Self::is_integer(end.number) {
+        if !Self::is_integer(end.as_f64()) {
             return Err(RuntimeError::get_invalid_value_error_builder()
             .for_function(me.get_name())
             .for_parameter("end")
@@ -52,8 +52,8 @@ function!(find_first, [
             .build());
         }
 
-        let start = std::cmp::max(start, zero).number.floor() as usize;
-        let end = std::cmp::min(end, infinite).number.floor() as usize;
+        let start = std::cmp::max(start, zero).as_f64().floor() as usize;
+        let end = std::cmp::min(end, infinite).as_f64().floor() as usize;
 
         let subject = &subject[start..end];
         if let Some(offset) = subject.windows(sub.len()).position(|window| window == sub) {
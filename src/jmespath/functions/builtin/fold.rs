@@ -0,0 +1,30 @@
+use crate::function;
+
+use crate::FunctionContext;
+use crate::Value;
+use crate::functions::ReturnValue;
+
+use crate::functions::DataType;
+use crate::functions::Function;
+use crate::functions::ParamTypes::*;
+use crate::functions::Parameter::{self, *};
+
+function!(fold, [
+    elements => Required(Of(DataType::Array)),
+    expr => Required(Of(DataType::ExpRef))
+    ], |_: &fold, args: &Vec<Value>, context: &dyn FunctionContext| {
+
+        let array = args[0].as_array().unwrap();
+        let ast = args[1].as_expref().unwrap();
+
+        // the first element is the initial accumulated value
+        let Some((first, rest)) = array.split_first() else {
+            return Ok(Value::Null);
+        };
+        let mut acc = first.clone();
+        for element in rest {
+            acc = crate::functions::builtin::ReduceImpl::step(context, ast, acc, element)?;
+        }
+        Ok(acc)
+    }
+);
@@ -0,0 +1,144 @@
+use crate::errors::error_builder::FunctionErrorBuilder;
+use crate::errors::error_builder::InvalidValueErrorBuilder;
+use crate::function;
+
+use crate::errors::Error as RuntimeError;
+use crate::errors::error_builder::ErrorBuilder;
+use crate::errors::invalid_value::InvalidValueErrorBuilderFactory;
+
+use crate::FunctionContext;
+use crate::Value;
+use crate::functions::ReturnValue;
+
+use crate::functions::DataType;
+use crate::functions::Function;
+use crate::functions::ParamTypes::*;
+use crate::functions::Parameter::{self, *};
+
+function!(get, [
+    subject => Required(Of(DataType::Any)),
+    key => Required(Any(vec![DataType::String, DataType::Number, DataType::Array])),
+    default => Optional(Of(DataType::Any))
+    ], |me: &get, args: &Vec<Value>, _: &dyn FunctionContext| {
+
+        // a single key is a path with one segment
+        let path = match &args[1] {
+            Value::Array(path) => path.as_slice(),
+            key => std::slice::from_ref(key),
+        };
+
+        let mut current = &args[0];
+        for segment in path {
+            let next = match segment {
+                Value::String(key) => current.as_object().and_then(|object| object.get(key)),
+                Value::Number(index) if Self::is_integer(index.as_f64()) => {
+                    current.as_array().and_then(|array| Self::element_at(array, index.as_f64()))
+                }
+                _ => {
+                    let expected = match &args[1] {
+                        Value::Array(..) => "an array of strings and integers",
+                        _ => "a string or an integer",
+                    };
+                    return Err(RuntimeError::get_invalid_value_error_builder()
+                        .for_function(me.get_name())
+                        .for_parameter("key")
+                        .expected(expected)
+                        .received(&args[1])
+                        .build());
+                }
+            };
+            match next {
+                Some(value) => current = value,
+                None => return Ok(args.get(2).cloned().unwrap_or(Value::Null)),
+            }
+        }
+        Ok(current.clone())
+});
+
+impl get {
+    pub(crate) fn is_integer(number: f64) -> bool {
+        number.floor() == number
+    }
+    // negative indices count from the end of the array
+    fn element_at(array: &[Value], index: f64) -> Option<&Value> {
+        let index = if index < 0.0 {
+            index + array.len() as f64
+        } else {
+            index
+        };
+        if index < 0.0 {
+            return None;
+        }
+        array.get(index as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::functions::builtin::test_utils::Fixture;
+    use crate::{FunctionContext, Value};
+    use rstest::*;
+
+    #[rstest]
+    #[case("1", r#"{"a": 1}"#, r#""a""#, None)]
+    #[case("null", r#"{"a": 1}"#, r#""b""#, None)]
+    #[case(r#""none""#, r#"{"a": 1}"#, r#""b""#, Some(r#""none""#))]
+    #[case("null", r#"{"a": null}"#, r#""a""#, Some(r#""none""#))]
+    #[case(r#""none""#, "[1, 2]", r#""a""#, Some(r#""none""#))]
+    #[case("2", "[1, 2]", "1", None)]
+    #[case("2", "[1, 2]", "-1", None)]
+    #[case("null", "[1, 2]", "2", None)]
+    #[case("null", "[1, 2]", "-3", None)]
+    #[case("null", r#"{"a": 1}"#, "0", None)]
+    #[case("3", r#"{"a": {"b": [1, {"c": 3}]}}"#, r#"["a", "b", 1, "c"]"#, None)]
+    #[case(
+        "0",
+        r#"{"a": {"b": [1, {"c": 3}]}}"#,
+        r#"["a", "x", 1, "c"]"#,
+        Some("0")
+    )]
+    #[case(r#"{"a": 1}"#, r#"{"a": 1}"#, "[]", None)]
+    fn get(
+        #[case] expected: &str,
+        #[case] subject: &str,
+        #[case] key: &str,
+        #[case] default: Option<&str>,
+    ) {
+        let fixture = Fixture::setup();
+        let context: &dyn FunctionContext = &fixture;
+
+        // call function
+
+        let fname = "get";
+        let mut args = vec![
+            Value::from_json(subject).unwrap(),
+            Value::from_json(key).unwrap(),
+        ];
+        if let Some(default) = default {
+            args.push(Value::from_json(default).unwrap());
+        }
+        let result = fixture.runtime.call(fname, &args, context).unwrap();
+
+        assert_eq!(Value::from_json(expected).unwrap(), result);
+    }
+
+    #[rstest]
+    #[case("1.5")]
+    #[case(r#"["a", 1.5]"#)]
+    #[case(r#"["a", true]"#)]
+    fn get_invalid_key(#[case] key: &str) {
+        let fixture = Fixture::setup();
+        let context: &dyn FunctionContext = &fixture;
+
+        let args = vec![
+            Value::from_json(r#"{"a": [1]}"#).unwrap(),
+            Value::from_json(key).unwrap(),
+        ];
+        let result = fixture
+            .runtime
+            .call("get", &args, context)
+            .map_err(|e| e.kind);
+
+        assert_eq!(Err(crate::errors::Kind::InvalidValue), result);
+    }
+}
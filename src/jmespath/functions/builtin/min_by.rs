@@ -66,6 +66,24 @@ mod tests {
                 closure: Box::new(closure),
             })
         }
+        fn invoke(&self, _: &crate::AST, _: &[crate::Value]) -> crate::functions::ReturnValue {
+            unimplemented!()
+        }
+        fn invoke_with_bindings(
+            &self,
+            _: &crate::AST,
+            _: &[crate::Value],
+            _: &crate::Map<String, crate::Value>,
+        ) -> crate::functions::ReturnValue {
+            unimplemented!()
+        }
+        fn evaluate_with_bindings(
+            &self,
+            _: &crate::AST,
+            _: &crate::Map<String, crate::Value>,
+        ) -> crate::functions::ReturnValue {
+            unimplemented!()
+        }
     }
 
     fn setup() -> Fixture {
@@ -6,7 +6,9 @@ pub mod ends_with;
 pub mod find_first;
 pub mod find_last;
 pub mod floor;
+pub mod fold;
 pub mod from_items;
+pub mod get;
 pub mod items;
 pub mod keys;
 pub mod length;
@@ -15,9 +17,22 @@ pub mod pad_impl;
 pub mod pad_left;
 pub mod pad_right;
 pub(crate) use pad_impl::PadImpl;
+pub mod reduce;
+pub mod reduce_impl;
+pub(crate) use reduce_impl::ReduceImpl;
 pub mod reverse;
+pub mod scan;
 pub mod starts_with;
 
+/// Returns the variables that a builtin function binds
+/// while evaluating its expression reference arguments.
+pub(crate) fn bound_variables(function_name: &str) -> &'static [&'static str] {
+    match function_name {
+        "reduce" | "fold" | "scan" => &[ReduceImpl::ACCUMULATOR],
+        _ => &[],
+    }
+}
+
 #[cfg(test)]
 mod test_utils {
 
@@ -43,5 +58,23 @@ mod test_utils {
         ) -> Result<crate::ByFunctionHolder<'a>, RuntimeError> {
             todo!()
         }
+        fn invoke(&self, _: &crate::AST, _: &[crate::Value]) -> crate::functions::ReturnValue {
+            todo!()
+        }
+        fn invoke_with_bindings(
+            &self,
+            _: &crate::AST,
+            _: &[crate::Value],
+            _: &crate::Map<String, crate::Value>,
+        ) -> crate::functions::ReturnValue {
+            todo!()
+        }
+        fn evaluate_with_bindings(
+            &self,
+            _: &crate::AST,
+            _: &crate::Map<String, crate::Value>,
+        ) -> crate::functions::ReturnValue {
+            todo!()
+        }
     }
 }
@@ -14,7 +14,7 @@ impl PadImpl {
 
     pub(crate) fn width_from_arg(me: &dyn Function, arg: &Value) -> Result<usize, RuntimeError> {
         let width = arg.as_number().unwrap();
-        if !Self::is_integer(width.number) {
+        if !Self::is_integer(width.as_f64()) {
             return Err(RuntimeError::get_invalid_value_error_builder()
                 .for_function(me.get_name())
                 .for_parameter("width")
@@ -22,7 +22,7 @@ impl PadImpl {
                 .received(arg)
                 .build());
         }
-        if width.number < 0.0 {
+        if width.as_f64() < 0.0 {
             return Err(RuntimeError::get_invalid_value_error_builder()
                 .for_function(me.get_name())
                 .for_parameter("width")
@@ -30,7 +30,7 @@ impl PadImpl {
                 .received(arg)
                 .build());
         }
-        Ok(width.number.floor() as usize)
+        Ok(width.as_f64().floor() as usize)
     }
 
     pub(crate) fn pad_char_from_args(
@@ -0,0 +1,27 @@
+use crate::function;
+
+use crate::FunctionContext;
+use crate::Value;
+use crate::functions::ReturnValue;
+
+use crate::functions::DataType;
+use crate::functions::Function;
+use crate::functions::ParamTypes::*;
+use crate::functions::Parameter::{self, *};
+
+function!(reduce, [
+    elements => Required(Of(DataType::Array)),
+    expr => Required(Of(DataType::ExpRef)),
+    initial => Required(Of(DataType::Any))
+    ], |_: &reduce, args: &Vec<Value>, context: &dyn FunctionContext| {
+
+        let array = args[0].as_array().unwrap();
+        let ast = args[1].as_expref().unwrap();
+
+        let mut acc = args[2].clone();
+        for element in array {
+            acc = crate::functions::builtin::ReduceImpl::step(context, ast, acc, element)?;
+        }
+        Ok(acc)
+    }
+);
@@ -0,0 +1,71 @@
+use crate::functions::ReturnValue;
+use crate::{AST, FunctionContext, Map, NodeType, Value};
+
+pub(crate) struct ReduceImpl;
+impl ReduceImpl {
+    /// The variable bound to the accumulated value.
+    pub(crate) const ACCUMULATOR: &'static str = "$acc";
+    /// Computes the next accumulated value from the current element.
+    ///
+    /// A lambda expression such as `($acc, $x) => ...` receives the accumulator
+    /// and the element as arguments. Any other expression is evaluated
+    /// against the element, with the accumulator bound to `$acc`.
+    pub(crate) fn step(
+        context: &dyn FunctionContext,
+        ast: &AST,
+        acc: Value,
+        element: &Value,
+    ) -> ReturnValue {
+        if matches!(ast.node_type, NodeType::LambdaExpression(..)) {
+            return context.invoke(ast, &[acc, element.clone()]);
+        }
+        let mut bindings = Map::new();
+        bindings.insert(Self::ACCUMULATOR.to_string(), acc);
+        context.invoke_with_bindings(ast, std::slice::from_ref(element), &bindings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Value;
+    use crate::errors::Kind;
+    use rstest::*;
+
+    #[rstest]
+    #[case("10", "reduce(numbers, &$acc + @, `0`)")]
+    #[case("10", "reduce(numbers, ($acc, $n) => $acc + $n, `0`)")]
+    #[case("[4, 3, 2, 1]", "reduce(numbers, &[@, $acc][], `[]`)")]
+    #[case(
+        r#"{"count": 4, "last": 4}"#,
+        r#"reduce(numbers, &{count: $acc.count + `1`, last: @}, `{"count": 0}`)"#
+    )]
+    #[case("3", "let $step = `1` in reduce(numbers[:3], &$acc + $step, `0`)")]
+    #[case(r#""init""#, "reduce(empty, &$acc + @, 'init')")]
+    #[case("10", "fold(numbers, &$acc + @)")]
+    #[case("10", "fold(numbers, ($acc, $n) => $acc + $n)")]
+    #[case("4", "fold(numbers, ($acc, $n) => $acc > $n && $acc || $n)")]
+    #[case("1", "fold(numbers[:1], &$acc + @)")]
+    #[case("null", "fold(empty, &$acc + @)")]
+    #[case("[1, 3, 6, 10]", "scan(numbers, &$acc + @, `0`)")]
+    #[case("[1, 3, 6, 10]", "scan(numbers, ($acc, $n) => $acc + $n, `0`)")]
+    #[case("[2, 4, 12, 48]", "scan(numbers, &$acc × @, `2`)")]
+    #[case("[[1], [1, 2]]", "scan(numbers[:2], &[$acc, [@]][], `[]`)")]
+    #[case("[]", "scan(empty, &$acc + @, `0`)")]
+    fn step(#[case] expected: &str, #[case] expression: &str) {
+        let root = Value::from_json(r#"{"numbers": [1, 2, 3, 4], "empty": []}"#).unwrap();
+        let result = crate::search(expression, &root).unwrap();
+        assert_eq!(
+            Value::from_json(expected).unwrap(),
+            result,
+            "{}",
+            expression
+        );
+    }
+
+    #[test]
+    fn step_propagates_errors() {
+        let result =
+            crate::search("reduce(`[1]`, &abs($acc), 'a')", &Value::Null).map_err(|e| e.kind);
+        assert_eq!(Err(Kind::InvalidType), result);
+    }
+}
@@ -489,6 +489,24 @@ mod tests {
         ) -> Result<ByFunctionHolder<'_>, RuntimeError> {
             unimplemented!()
         }
+        fn invoke(&self, _: &crate::AST, _: &[crate::Value]) -> crate::functions::ReturnValue {
+            unimplemented!()
+        }
+        fn invoke_with_bindings(
+            &self,
+            _: &crate::AST,
+            _: &[crate::Value],
+            _: &crate::Map<String, crate::Value>,
+        ) -> crate::functions::ReturnValue {
+            unimplemented!()
+        }
+        fn evaluate_with_bindings(
+            &self,
+            _: &crate::AST,
+            _: &crate::Map<String, crate::Value>,
+        ) -> crate::functions::ReturnValue {
+            unimplemented!()
+        }
     }
 
     fn setup() -> Fixture {
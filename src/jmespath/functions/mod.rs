@@ -1,5 +1,6 @@
 mod function;
 mod types;
+mod user_function;
 
 pub(crate) mod signature;
 
@@ -16,3 +17,4 @@ pub use signature::ParamTypes;
 pub use signature::Parameter;
 pub use signature::Signature;
 pub use types::DataType;
+pub use user_function::UserFunction;
@@ -0,0 +1,322 @@
+use std::rc::Rc;
+
+use santiago::lexer::Lexeme;
+
+use super::{DataType, Function, ParamTypes, Parameter, ReturnValue, Signature};
+use crate::errors::Error as RuntimeError;
+use crate::errors::error_builder::{ErrorBuilder, SyntaxErrorBuilder};
+use crate::errors::syntax::SyntaxErrorBuilderFactory;
+use crate::lexer::{SourceMap, tokenize};
+use crate::{AST, FunctionContext, Map, Value, parse};
+
+/// Represents a JMESPath [`Function`] whose implementation is itself a JMESPath expression.
+///
+/// When the function is invoked, its arguments are bound to the named parameters
+/// as variables, just like in a let-expression. The body of the function only has
+/// access to its parameters: the current node `@` evaluates to `null` and the
+/// root node `$` refers to the JSON document being searched.
+///
+/// Use the [`Runtime::define()`](crate::Runtime::define()) function to register
+/// a function from its definition.
+///
+/// # Example
+/// ```
+/// use jmespath_community as jmespath;
+/// use jmespath::functions::{DataType, ParamTypes::*, Parameter::*, UserFunction};
+/// use jmespath::{parse, FunctionRegistrar, Runtime, Value};
+///
+/// let function = UserFunction::new(
+///     "double",
+///     vec![("$n", Required(Of(DataType::Number)))],
+///     parse("$n * `2`").unwrap(),
+/// );
+///
+/// let mut runtime = Runtime::create_runtime();
+/// runtime.register(Box::new(function));
+///
+/// let result = runtime.search("double(`21`)", &Value::Null).unwrap();
+/// assert_eq!(42, result);
+/// ```
+pub struct UserFunction {
+    name: String,
+    parameter_names: Vec<String>,
+    signature: Vec<Parameter>,
+    body: AST,
+}
+impl UserFunction {
+    /// Creates a new instance of the [`UserFunction`] type.
+    ///
+    /// Each parameter is named after the variable it binds in the body, such as `$p`.
+    pub fn new(name: &str, parameters: Vec<(&str, Parameter)>, body: AST) -> Self {
+        let (parameter_names, signature) = parameters
+            .into_iter()
+            .map(|(name, parameter)| (name.to_string(), parameter))
+            .unzip();
+        UserFunction {
+            name: name.to_string(),
+            parameter_names,
+            signature,
+            body,
+        }
+    }
+    /// Parses a function definition such as `fullname($p) = join(' ', [$p.first, $p.last])`.
+    ///
+    /// Every parameter is required and accepts arguments of any type.
+    ///
+    /// # Example
+    /// ```
+    /// use jmespath_community as jmespath;
+    /// use jmespath::functions::{Function, UserFunction};
+    ///
+    /// let function = UserFunction::parse("fullname($p) = join(' ', [$p.first, $p.last])").unwrap();
+    /// assert_eq!("fullname", function.get_name());
+    /// assert_eq!("$p", function.get_parameter_name(0));
+    /// ```
+    pub fn parse(definition: &str) -> Result<Self, RuntimeError> {
+        let tokens = tokenize(definition)?;
+        let source_map = SourceMap::new(definition);
+        let mut header = Header {
+            tokens: tokens.iter(),
+            source_map: &source_map,
+        };
+
+        let name = header.expect("unquoted_string", "a function name")?;
+        header.expect("lparen", "`(`")?;
+        let mut parameters: Vec<&Rc<Lexeme>> = Vec::new();
+        if header.peek_kind() == Some("rparen") {
+            header.expect("rparen", "`)`")?;
+        } else {
+            loop {
+                let parameter = header.expect("variable_ref", "a parameter")?;
+                if parameters.iter().any(|other| other.raw == parameter.raw) {
+                    let reason = format!("duplicate parameter '{}'", parameter.raw);
+                    return Err(header.error_at(parameter, &reason));
+                }
+                parameters.push(parameter);
+                if header.peek_kind() != Some("comma") {
+                    header.expect("rparen", "`,` or `)`")?;
+                    break;
+                }
+                header.expect("comma", "`,`")?;
+            }
+        }
+        header.expect("assign", "`=`")?;
+        let Some(first) = header.tokens.clone().next() else {
+            return Err(header.error_at_end("the function body"));
+        };
+
+        // blank out the header so that positions in the body
+        // match positions in the definition
+        let offset = source_map.offset(first.position.line, first.position.column);
+        let blank: String = definition[..offset]
+            .chars()
+            .map(|c| if c == '\n' { '\n' } else { ' ' })
+            .collect();
+        let body = parse(&format!("{}{}", blank, &definition[offset..]))?;
+
+        let parameters = parameters
+            .iter()
+            .map(|parameter| {
+                (
+                    parameter.raw.as_str(),
+                    Parameter::Required(ParamTypes::Of(DataType::Any)),
+                )
+            })
+            .collect();
+        Ok(Self::new(&name.raw, parameters, body))
+    }
+}
+impl Function for UserFunction {
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+    fn get_signature(&self) -> &Vec<Parameter> {
+        &self.signature
+    }
+    fn execute(&self, args: &Vec<Value>, context: &dyn FunctionContext) -> ReturnValue {
+        let mut bindings = Map::new();
+        for (index, name) in self.parameter_names.iter().enumerate() {
+            let is_last = index + 1 == self.parameter_names.len();
+            let value = if is_last && Signature::is_variadic(&self.signature) {
+                Value::Array(args.iter().skip(index).cloned().collect())
+            } else {
+                args.get(index).cloned().unwrap_or(Value::Null)
+            };
+            bindings.insert(name.clone(), value);
+        }
+        // positions within the body are meaningless to the caller
+        context
+            .evaluate_with_bindings(&self.body, &bindings)
+            .map_err(|mut err| {
+                err.position = None;
+                err.span = None;
+                err
+            })
+    }
+    fn get_parameter_name(&self, index: usize) -> String {
+        let index = index.min(self.parameter_names.len().saturating_sub(1));
+        match self.parameter_names.get(index) {
+            Some(name) => name.clone(),
+            None => format!("param{}", index),
+        }
+    }
+}
+
+// walks the `name($a, $b) =` header of a function definition
+struct Header<'a> {
+    tokens: std::slice::Iter<'a, Rc<Lexeme>>,
+    source_map: &'a SourceMap,
+}
+impl<'a> Header<'a> {
+    fn peek_kind(&self) -> Option<&'a str> {
+        self.tokens.clone().next().map(|token| token.kind.as_str())
+    }
+    fn expect(&mut self, kind: &str, description: &str) -> Result<&'a Rc<Lexeme>, RuntimeError> {
+        match self.tokens.next() {
+            Some(token) if token.kind == kind => Ok(token),
+            Some(token) => {
+                let reason = format!("expected {}, found '{}'", description, token.raw);
+                Err(self.error_at(token, &reason))
+            }
+            None => Err(self.error_at_end(description)),
+        }
+    }
+    fn error_at(&self, token: &Lexeme, reason: &str) -> RuntimeError {
+        RuntimeError::get_syntax_error_builder()
+            .at_span(self.source_map.span(token))
+            .for_reason(reason)
+            .build()
+    }
+    fn error_at_end(&self, description: &str) -> RuntimeError {
+        let reason = format!("expected {}, found end of definition", description);
+        RuntimeError::get_syntax_error_builder()
+            .at_span(self.source_map.end())
+            .for_reason(&reason)
+            .build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::{Kind, Position};
+    use crate::{FunctionRegistrar, Runtime};
+    use rstest::*;
+
+    fn setup(definitions: &[&str]) -> Runtime {
+        let mut runtime = Runtime::create_runtime();
+        for definition in definitions {
+            runtime.define(definition).unwrap();
+        }
+        runtime
+    }
+
+    #[rstest]
+    #[case(
+        "fullname($p) = [$p.first, $p.last]",
+        "people[*].fullname(@)",
+        r#"[["Ada","Lovelace"],["Alan","Turing"]]"#
+    )]
+    #[case("answer() = `42`", "answer()", "42")]
+    #[case("pick($a, $b) = [$b, $a]", "pick(`1`, `2`)", "[2,1]")]
+    #[case("first_person() = $.people[0].first", "first_person()", r#""Ada""#)]
+    #[case("current() = @", "current()", "null")]
+    #[case(
+        "# the number of people\ncount($p) =\n  length($p)",
+        "count(people)",
+        "2"
+    )]
+    fn user_function(#[case] definition: &str, #[case] expression: &str, #[case] expected: &str) {
+        let runtime = setup(&[definition]);
+        let root = Value::from_json(
+            r#"{"people": [{"first": "Ada", "last": "Lovelace"}, {"first": "Alan", "last": "Turing"}]}"#,
+        )
+        .unwrap();
+        let result = runtime.search(expression, &root).unwrap();
+        assert_eq!(Value::from_json(expected).unwrap(), result);
+    }
+
+    #[test]
+    fn user_function_calls_user_function() {
+        let runtime = setup(&[
+            "double($n) = $n * `2`",
+            "quadruple($n) = double(double($n))",
+        ]);
+        let result = runtime.search("quadruple(`3`)", &Value::Null).unwrap();
+        assert_eq!(12, result);
+    }
+
+    #[test]
+    fn user_function_recursion() {
+        let runtime = setup(&["factorial($n) = $n <= `1` ? `1` : ($n * factorial($n - `1`))"]);
+        let result = runtime.search("factorial(`5`)", &Value::Null).unwrap();
+        assert_eq!(120, result);
+    }
+
+    #[test]
+    fn user_function_infinite_recursion() {
+        let runtime = setup(&["forever($n) = forever($n)"]);
+        let err = runtime.search("forever(`1`)", &Value::Null).unwrap_err();
+        assert_eq!(Kind::InvalidValue, err.kind);
+        assert_eq!(Some(Position::new(1, 1)), err.position);
+    }
+
+    #[test]
+    fn user_function_does_not_capture_caller_variables() {
+        let runtime = setup(&["leak() = $x"]);
+        let err = runtime
+            .search("let $x = `1` in leak()", &Value::Null)
+            .unwrap_err();
+        assert_eq!(Kind::UndefinedVariable, err.kind);
+        assert_eq!(Some(Position::new(1, 17)), err.position);
+    }
+
+    #[rstest]
+    #[case("answer(`1`)", Kind::InvalidArity)]
+    #[case("typed(`1`)", Kind::InvalidType)]
+    fn user_function_checks_arguments(#[case] expression: &str, #[case] kind: Kind) {
+        let mut runtime = setup(&["answer() = `42`"]);
+        runtime.register(Box::new(UserFunction::new(
+            "typed",
+            vec![("$s", Parameter::Required(ParamTypes::Of(DataType::String)))],
+            crate::parse("$s").unwrap(),
+        )));
+        let err = runtime.search(expression, &Value::Null).unwrap_err();
+        assert_eq!(kind, err.kind);
+    }
+
+    #[test]
+    fn user_function_variadic() {
+        let mut runtime = Runtime::create_runtime();
+        runtime.register(Box::new(UserFunction::new(
+            "tail",
+            vec![
+                ("$head", Parameter::Required(ParamTypes::Of(DataType::Any))),
+                ("$rest", Parameter::Variadic(ParamTypes::Of(DataType::Any))),
+            ],
+            crate::parse("$rest").unwrap(),
+        )));
+        let result = runtime.search("tail(`1`, `2`, `3`)", &Value::Null).unwrap();
+        assert_eq!(Value::from_json("[2, 3]").unwrap(), result);
+    }
+
+    #[rstest]
+    #[case("(1, 1)", "($p) = $p")]
+    #[case("(1, 5)", "foo $p = $p")]
+    #[case("(1, 5)", "foo(p) = p")]
+    #[case("(1, 8)", "foo($p $q) = $p")]
+    #[case("(1, 9)", "foo($p, $p) = $p")]
+    #[case("(1, 9)", "foo($p) $p")]
+    #[case("(1, 10)", "foo($p) =")]
+    #[case("(2, 6)", "foo($p) =\n  $p.")]
+    fn user_function_syntax_error(#[case] position: &str, #[case] definition: &str) {
+        let err = UserFunction::parse(definition).err().unwrap();
+        assert_eq!(Kind::Syntax, err.kind, "{}", definition);
+        assert_eq!(
+            position,
+            format!("{}", err.position.unwrap()),
+            "{}",
+            definition
+        );
+    }
+}
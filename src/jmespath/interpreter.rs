@@ -1,34 +1,65 @@
 use crate::Map;
+use crate::Number;
 use crate::Value;
+use crate::batch::SharedResults;
 use crate::errors::error_builder::{
-    ErrorBuilder, FunctionErrorBuilder, InvalidTypeErrorBuilder, NotANumberErrorBuilder,
-    SyntaxErrorBuilder,
+    ErrorBuilder, FunctionErrorBuilder, InvalidTypeErrorBuilder, InvalidValueErrorBuilder,
+    NotANumberErrorBuilder, SyntaxErrorBuilder,
 };
 use crate::errors::invalid_type::InvalidTypeErrorBuilderFactory;
+use crate::errors::invalid_value::InvalidValueErrorBuilderFactory;
 use crate::errors::not_a_number::NotANumberErrorBuilderFactory;
 use crate::errors::syntax::SyntaxErrorBuilderFactory;
 use crate::errors::{Error as RuntimeError, Kind, Position};
 use crate::functions::{DataType, Function, ParamTypes, ReturnValue};
+use crate::json_like::{JsonLike, Node};
 use crate::parser::{AST, Slice};
+use crate::path::{Located, Trail};
+use crate::prepared::unbound_placeholder;
 use crate::runtime::{ByFunctionHolder, Runtime};
 use crate::scopes::Scopes;
-use crate::value_option::ValueOption as _;
 use crate::{FunctionContext, NodeType};
 
-pub struct Interpreter<'a> {
-    root: Option<&'a Value>,
+/// The maximum number of nested calls to functions implemented as JMESPath expressions.
+const MAX_CALL_DEPTH: usize = 64;
+
+/// The result of evaluating an expression against a node of the document.
+type NodeResult<'j, J> = Result<Node<'j, J>, RuntimeError>;
+
+pub struct Interpreter<'a, 'j, J: JsonLike = Value> {
+    root: Option<&'j J>,
     runtime: &'a Runtime,
     scopes: Option<Box<Scopes<'a>>>,
+    depth: usize,
+    track_paths: bool,
+    shared: Option<&'a SharedResults<'j, J>>,
 }
-impl<'a> Interpreter<'a> {
-    pub fn new(runtime: &'a Runtime, root: &'a Value) -> Self {
+impl<'a, 'j, J: JsonLike> Interpreter<'a, 'j, J> {
+    pub fn new(runtime: &'a Runtime, root: &'j J) -> Self {
         Interpreter {
             root: Some(root),
             runtime,
             scopes: None,
+            depth: 0,
+            track_paths: false,
+            shared: None,
         }
     }
-    pub fn with_scope(&'a self, bindings: &'a Map<String, Value>) -> Self {
+    /// Records the location of the values taken from the JSON document.
+    ///
+    /// See [`Interpreter::evaluate_paths()`].
+    pub fn tracking_paths(mut self) -> Self {
+        self.track_paths = true;
+        self
+    }
+    /// Reuses the results of sub-expressions shared between several expressions.
+    ///
+    /// Shared results are only reused when evaluating against the root node.
+    pub fn sharing(mut self, shared: &'a SharedResults<'j, J>) -> Self {
+        self.shared = Some(shared);
+        self
+    }
+    pub fn with_scope<'b>(&'b self, bindings: &'b Map<String, Value>) -> Interpreter<'b, 'j, J> {
         let inner_scope = match &self.scopes {
             None => Box::new(Scopes::new(bindings)),
             Some(s) => Box::new(s.with_scope(bindings)),
@@ -37,71 +68,100 @@ impl<'a> Interpreter<'a> {
             root: self.root,
             runtime: self.runtime,
             scopes: Some(inner_scope),
+            depth: self.depth,
+            track_paths: self.track_paths,
+            shared: self.shared,
         }
     }
     pub fn evaluate(&self, ast: &AST) -> ReturnValue {
-        self.visit(ast, self.root.unwrap())
+        self.visit(ast, &self.root_node()).map(Node::into_value)
+    }
+    /// Evaluates an expression and pairs each result with its location.
+    ///
+    /// The elements selected by a projection are returned individually.
+    /// Values taken directly from the JSON document have a path if
+    /// [`Interpreter::tracking_paths()`] is enabled, whereas values constructed
+    /// by the expression are synthetic.
+    pub fn evaluate_paths(&self, ast: &AST) -> Result<Vec<Located>, RuntimeError> {
+        let mut located = Vec::new();
+        self.visit(ast, &self.root_node())?
+            .into_located(&mut located);
+        Ok(located)
+    }
+    fn root_node(&self) -> Node<'j, J> {
+        Node::Borrowed(self.root.unwrap(), Trail::root(self.track_paths))
     }
 
-    fn visit_raw_string(&self, ast: &AST, _: &Value) -> ReturnValue {
-        Ok(ast.raw_string().into())
+    fn visit_raw_string(&self, ast: &AST, _: &Node<'j, J>) -> NodeResult<'j, J> {
+        Ok(Node::Owned(ast.raw_string().into()))
     }
-    fn visit_current_node(&self, _: &AST, value: &Value) -> ReturnValue {
+    fn visit_current_node(&self, _: &AST, value: &Node<'j, J>) -> NodeResult<'j, J> {
         Ok(value.clone())
     }
-    fn visit_root_node(&self, _: &AST, _: &Value) -> ReturnValue {
-        Ok(self.root.unwrap().clone())
+    fn visit_root_node(&self, _: &AST, _: &Node<'j, J>) -> NodeResult<'j, J> {
+        Ok(self.root_node())
     }
 
     fn visit_quoted_identifier(
         &self,
         ast: &AST,
-        value: &Value,
+        value: &Node<'j, J>,
         position: &Position,
-    ) -> ReturnValue {
+    ) -> NodeResult<'j, J> {
         let identifier = Self::unwrap_quoted_identifier(ast.identifier(), *position)?;
-        Ok(match value {
-            Value::Expression(_) => unreachable!(),
-            Value::Object(v) => {
-                if v.contains_key(&identifier) {
-                    v[&identifier].clone()
-                } else {
-                    Value::Null
-                }
-            }
-            _ => Value::Null,
-        })
+        Ok(value.get(&identifier))
     }
 
-    fn visit_identifier(&self, ast: &AST, value: &Value) -> ReturnValue {
-        let identifier = ast.identifier();
-        Ok(match value {
-            Value::Expression(_) => unreachable!(),
-            Value::Object(v) => {
-                if v.contains_key(identifier) {
-                    v[identifier].clone()
-                } else {
-                    Value::Null
-                }
-            }
-            _ => Value::Null,
-        })
+    fn visit_identifier(&self, ast: &AST, value: &Node<'j, J>) -> NodeResult<'j, J> {
+        Ok(value.get(ast.identifier()))
     }
 
-    fn visit_variable_ref(&self, ast: &AST, _: &Value) -> ReturnValue {
+    fn visit_variable_ref(&self, ast: &AST, _: &Node<'j, J>) -> NodeResult<'j, J> {
         let variable_name = ast.variable_ref();
-        match self.scopes.as_ref().map(|x| x.get(variable_name)).flatten() {
-            Some(v) => Ok(v.clone()),
+        let scoped = self.scopes.as_ref().map(|x| x.get(variable_name)).flatten();
+        match scoped.or_else(|| self.runtime.get_global(variable_name)) {
+            Some(v) => Ok(Node::Owned(v.clone())),
             _ => Err(RuntimeError::undefined_variable(variable_name)),
         }
     }
 
-    fn visit_expref(&self, nodes: &Vec<AST>, _: &Value) -> ReturnValue {
+    fn visit_placeholder(&self, ast: &AST, _: &Node<'j, J>) -> NodeResult<'j, J> {
+        let name = ast.placeholder();
+        match self.scopes.as_ref().and_then(|x| x.get(name)) {
+            Some(v) => Ok(Node::Owned(v.clone())),
+            None => Err(unbound_placeholder(name, None)),
+        }
+    }
+
+    fn visit_expref(&self, nodes: &Vec<AST>, _: &Node<'j, J>) -> NodeResult<'j, J> {
         assert_eq!(1, nodes.len());
-        Ok(Value::Expression(nodes[0].clone()))
+        Ok(Node::Owned(Value::Expression(nodes[0].clone())))
+    }
+    /// Invokes an expression-type with positional arguments.
+    ///
+    /// The current node is the first argument.
+    /// Arguments to a lambda expression are bound to its parameters.
+    pub(crate) fn invoke_expression(&self, ast: &AST, args: &[Value]) -> ReturnValue {
+        let current = Node::Owned(args.first().cloned().unwrap_or(Value::Null));
+        let NodeType::LambdaExpression(nodes) = &ast.node_type else {
+            return self.visit(ast, &current).map(Node::into_value);
+        };
+        let (body, parameters) = nodes.split_last().unwrap();
+        let mut bindings: Map<String, Value> = Map::new();
+        for (index, parameter) in parameters.iter().enumerate() {
+            let value = args.get(index).cloned().unwrap_or(Value::Null);
+            bindings.insert(parameter.variable_ref().to_string(), value);
+        }
+        self.with_scope(&bindings)
+            .visit(body, &current)
+            .map(Node::into_value)
     }
 
-    fn visit_multi_select_hash(&self, map: &Map<String, AST>, value: &Value) -> ReturnValue {
+    fn visit_multi_select_hash(
+        &self,
+        map: &Map<String, AST>,
+        value: &Node<'j, J>,
+    ) -> NodeResult<'j, J> {
         let mut object: Map<String, Value> = Map::new();
         for item in map {
             // unwrap quoted identifier
@@ -111,74 +171,115 @@ impl<'a> Interpreter<'a> {
                 item.0.clone()
             };
             let evaluated = self.visit(&item.1, value)?;
-            object.insert(key, evaluated);
+            object.insert(key, evaluated.into_value());
         }
-        Ok(Value::Object(object))
+        Ok(Node::Owned(Value::Object(object)))
     }
 
-    fn visit_multi_select_list(&self, nodes: &Vec<AST>, value: &Value) -> ReturnValue {
+    fn visit_multi_select_list(&self, nodes: &Vec<AST>, value: &Node<'j, J>) -> NodeResult<'j, J> {
         let mut array = Vec::new();
         for node in nodes {
-            array.push(self.visit(node, value)?);
+            array.push(self.visit(node, value)?.into_value());
         }
-        Ok(Value::Array(array))
+        Ok(Node::Owned(Value::Array(array)))
     }
 
-    fn visit_pipe_expression(&self, nodes: &Vec<AST>, value: &Value) -> ReturnValue {
+    fn visit_pipe_expression(&self, nodes: &Vec<AST>, value: &Node<'j, J>) -> NodeResult<'j, J> {
         assert_eq!(2, nodes.len());
         let left = self.visit(&nodes[0], value)?;
         self.visit(&nodes[1], &left)
     }
-    fn visit_sub_expression(&self, nodes: &Vec<AST>, value: &Value) -> ReturnValue {
+    fn visit_sub_expression(&self, nodes: &Vec<AST>, value: &Node<'j, J>) -> NodeResult<'j, J> {
         assert_eq!(2, nodes.len());
         let left = self.visit(&nodes[0], value)?;
-        if let Value::Null = left {
-            return Ok(Value::Null);
+        if left.is_null() {
+            return Ok(Node::null());
         }
         self.visit(&nodes[1], &left)
     }
 
-    fn visit_hash_wildcard_projection(&self, nodes: &Vec<AST>, value: &Value) -> ReturnValue {
+    fn visit_hash_wildcard_projection(
+        &self,
+        nodes: &Vec<AST>,
+        value: &Node<'j, J>,
+    ) -> NodeResult<'j, J> {
         let object = match &nodes[0].node_type {
             NodeType::None => Ok(value.clone()),
             _ => self.visit(&nodes[0], value),
         }?;
-        Ok(match object {
-            Value::Object(map) => {
-                let values = map
-                    .values()
-                    .filter(|x| !x.is_null())
-                    .map(|x| x.clone())
-                    .collect::<Vec<Value>>();
-                if let NodeType::None = nodes[1].node_type {
-                    Value::Array(values)
-                } else {
-                    let mut result = Vec::new();
-                    for item in values {
-                        let right = self.visit(&nodes[1], &item)?;
-                        if !right.is_null() {
-                            result.push(right);
-                        }
-                    }
-                    Value::Array(result)
+        let Some(values) = object.into_values() else {
+            return Ok(Node::null());
+        };
+        let values = values.into_iter().filter(|x| !x.is_null());
+        let mut result = Vec::new();
+        if let NodeType::None = nodes[1].node_type {
+            result.extend(values);
+        } else {
+            for item in values {
+                let right = self.visit(&nodes[1], &item)?;
+                if !right.is_null() {
+                    result.push(right);
                 }
             }
-            _ => Value::Null,
-        })
+        }
+        Ok(Node::Array(result))
     }
-    fn visit_projection(&self, nodes: &Vec<AST>, value: &Value) -> ReturnValue {
+    fn visit_descendant_projection(&self, nodes: &[AST], value: &Node<'j, J>) -> NodeResult<'j, J> {
+        let left = match &nodes[0].node_type {
+            NodeType::None => Ok(value.clone()),
+            _ => self.visit(&nodes[0], value),
+        }?;
+        if !matches!(left.data_type(), DataType::Object | DataType::Array) {
+            return Ok(Node::null());
+        }
+        let mut result = Vec::new();
+        self.descend(&nodes[1], left, &mut result)?;
+        Ok(Node::Array(result))
+    }
+    /// Evaluates an expression against the specified value and
+    /// every object or array nested under it, in document order.
+    fn descend(
+        &self,
+        ast: &AST,
+        value: Node<'j, J>,
+        result: &mut Vec<Node<'j, J>>,
+    ) -> Result<(), RuntimeError> {
+        if !matches!(value.data_type(), DataType::Object | DataType::Array) {
+            return Ok(());
+        }
+        let right = self.visit(ast, &value)?;
+        if !right.is_null() {
+            result.push(right);
+        }
+        let children = match value.data_type() {
+            DataType::Array => value.into_elements(),
+            _ => value.into_values(),
+        };
+        for child in children.unwrap_or_default() {
+            self.descend(ast, child, result)?;
+        }
+        Ok(())
+    }
+    fn visit_projection(&self, nodes: &Vec<AST>, value: &Node<'j, J>) -> NodeResult<'j, J> {
         let left = match &nodes[0].node_type {
             NodeType::Filter(..) => self.filter(nodes, value),
             NodeType::Flatten => self.flatten(&nodes[1], value),
             NodeType::ListWildcard => self.list_wildcard(&nodes[1], value),
-            NodeType::Slice(slice) => self.slice(slice, &nodes[1], value, nodes[0].position),
+            NodeType::Slice(..) | NodeType::SliceExpression(..) => {
+                self.slice(&nodes[0], &nodes[1], value)
+            }
             _ => unreachable!(),
         }?;
         if let NodeType::None = nodes[2].node_type {
             return Ok(left);
         };
-        let result = match left {
-            Value::Array(array) => {
+        if left.data_type() == DataType::String {
+            // a slice projection with a second argument
+            // is really a sub-expression in disguise
+            return self.visit(&nodes[2], &left);
+        }
+        match left.into_elements() {
+            Some(array) => {
                 let mut result = Vec::new();
                 for item in array {
                     let right = self.visit(&nodes[2], &item)?;
@@ -186,79 +287,62 @@ impl<'a> Interpreter<'a> {
                         result.push(right);
                     }
                 }
-                Value::Array(result)
+                Ok(Node::Array(result))
             }
-            Value::String(..) => {
-                // a slice projection with a second argument
-                // is really a sub-expression in disguise
-                self.visit(&nodes[2], &left)?
-            }
-            _ => Value::Null,
-        };
-        Ok(result.into())
+            None => Ok(Node::null()),
+        }
     }
-    fn filter(&self, nodes: &Vec<AST>, value: &Value) -> ReturnValue {
+    fn filter(&self, nodes: &Vec<AST>, value: &Node<'j, J>) -> NodeResult<'j, J> {
         let array = match &nodes[1].node_type {
             NodeType::None => Ok(value.clone()),
             _ => self.visit(&nodes[1], value),
         }?;
-        Ok(match array {
-            Value::Array(array) => {
-                let result = match &nodes[0].node_type {
-                    NodeType::Filter(ast) => {
-                        let mut result = Vec::new();
-                        for item in array {
-                            let filtered = self.visit(&ast[0], &item)?;
-                            if filtered.is_truthy() {
-                                result.push(item)
-                            }
-                        }
-                        result
-                    }
-                    _ => unreachable!(),
-                };
-                Value::Array(result)
-            }
-            _ => Value::Null,
-        })
-    }
-    fn flatten(&self, node: &AST, value: &Value) -> ReturnValue {
-        // define a local 'flatten_array' function
-        fn flatten_array<'b>(array: &Vec<Value>, result: &'b mut Vec<Value>) -> &'b Vec<Value> {
-            for item in array {
-                if let Value::Array(nested_array) = item {
-                    for nested_item in nested_array {
-                        result.push(nested_item.clone())
-                    }
-                } else {
-                    result.push(item.clone())
-                }
+        let Some(array) = array.into_elements() else {
+            return Ok(Node::null());
+        };
+        let NodeType::Filter(ast) = &nodes[0].node_type else {
+            unreachable!()
+        };
+        let mut result = Vec::new();
+        for item in array {
+            let filtered = self.visit(&ast[0], &item)?;
+            if filtered.is_truthy() {
+                result.push(item)
             }
-            result
         }
+        Ok(Node::Array(result))
+    }
+    fn flatten(&self, node: &AST, value: &Node<'j, J>) -> NodeResult<'j, J> {
         // flatten the current node
         let array = match &node.node_type {
             NodeType::None => Ok(value.clone()),
             _ => self.visit(node, value),
         }?;
-        Ok(match array {
-            Value::Array(array) => {
-                let mut result: Vec<Value> = Vec::new();
-                let flat = flatten_array(&array, &mut result);
-                Value::Array(flat.clone())
+        let Some(array) = array.into_elements() else {
+            return Ok(Node::null());
+        };
+        let mut result = Vec::new();
+        for item in array {
+            if item.data_type() != DataType::Array {
+                result.push(item);
+                continue;
             }
-            _ => Value::Null,
-        })
+            result.extend(item.into_elements().unwrap_or_default());
+        }
+        Ok(Node::Array(result))
     }
-    fn list_wildcard(&self, node: &AST, value: &Value) -> ReturnValue {
-        let array: Result<Value, RuntimeError> = if matches!(node.node_type, NodeType::None) {
+    fn list_wildcard(&self, node: &AST, value: &Node<'j, J>) -> NodeResult<'j, J> {
+        let array = if matches!(node.node_type, NodeType::None) {
             Ok(value.clone())
         } else {
             self.visit(node, value)
-        };
-        Ok(array?.if_array().or_null())
+        }?;
+        Ok(array
+            .into_elements()
+            .map(Node::Array)
+            .unwrap_or(Node::null()))
     }
-    fn slice(&self, slice: &Slice, node: &AST, value: &Value, position: Position) -> ReturnValue {
+    fn slice(&self, slice: &AST, node: &AST, value: &Node<'j, J>) -> NodeResult<'j, J> {
         // define a local function to compute slice parameters
         fn compute_slice_params(
             slice: &Slice,
@@ -274,18 +358,24 @@ impl<'a> Interpreter<'a> {
                     .for_reason("slice step cannot be 0")
                     .build());
             };
+            // clamp bounds to the array, so that large bounds neither
+            // overflow nor iterate beyond its elements
+            let (lower, upper) = if step > 0 { (0, len) } else { (-1, len - 1) };
+            let adjust = |x: isize| {
+                if x < 0 {
+                    x.saturating_add(len).max(lower)
+                } else {
+                    x.min(upper)
+                }
+            };
             let start = slice
                 .start
-                .map(|x| if x < 0 { x + len } else { x })
-                .unwrap_or(if step > 0 {
-                    0
-                } else {
-                    (array_length - 1) as isize
-                });
+                .map(adjust)
+                .unwrap_or(if step > 0 { 0 } else { len - 1 });
             let stop = slice
                 .stop
-                .map(|x| if x < 0 { x + len } else { x })
-                .unwrap_or(if step > 0 { array_length as isize } else { -1 });
+                .map(adjust)
+                .unwrap_or(if step > 0 { len } else { -1 });
             Ok((start, stop, step))
         }
         // define a local function to slice an array
@@ -309,64 +399,94 @@ impl<'a> Interpreter<'a> {
             NodeType::None => Ok(value.clone()),
             _ => self.visit(node, value),
         }?;
-        match array {
-            Value::Array(input) => {
+        if !matches!(array.data_type(), DataType::Array | DataType::String) {
+            return Ok(Node::null());
+        }
+        let position = slice.position;
+        let slice = match &slice.node_type {
+            NodeType::Slice(slice) => slice.clone(),
+            NodeType::SliceExpression(bounds) => Slice {
+                start: self.bound(&bounds[0], &array)?,
+                stop: self.bound(&bounds[1], &array)?,
+                step: self.bound(&bounds[2], &array)?,
+            },
+            _ => unreachable!(),
+        };
+        let slice = &slice;
+        if let Some(text) = array.as_str() {
+            // slicing string by converting to an array of chars
+            let characters: Vec<char> = text.chars().collect();
+            let params = compute_slice_params(slice, characters.len(), position)?;
+            let sliced = slice_array(&characters, params.0, params.1, params.2);
+            return Ok(Node::Owned(Value::String(String::from_iter(sliced))));
+        }
+        match array.into_elements() {
+            Some(input) => {
                 let params = compute_slice_params(slice, input.len(), position)?;
                 let vector = slice_array(&input, params.0, params.1, params.2);
-                Ok(Value::Array(vector))
-            }
-            Value::String(text) => {
-                // slicing string by converting to an array of chars
-                let characters: Vec<char> = text.chars().collect();
-                let params = compute_slice_params(slice, characters.len(), position)?;
-                let sliced = slice_array(&characters, params.0, params.1, params.2);
-                Ok(Value::String(String::from_iter(sliced)))
+                Ok(Node::Array(vector))
             }
-            _ => Ok(Value::Null),
+            None => Ok(Node::null()),
         }
     }
 
-    fn visit_index_expression(&self, nodes: &Vec<AST>, value: &Value) -> ReturnValue {
+    fn visit_index_expression(&self, nodes: &Vec<AST>, value: &Node<'j, J>) -> NodeResult<'j, J> {
         let array = match &nodes[0].node_type {
             NodeType::None => Ok(value.clone()),
             _ => self.visit(&nodes[0], value),
         }?;
-
-        Ok(match array {
-            Value::Array(v) => {
-                let index = nodes[1].number();
-                let index = if index >= 0 {
-                    index
-                } else {
-                    index + v.len() as i32
-                };
-                match TryInto::<usize>::try_into(index) {
-                    Ok(i) => {
-                        if i < v.len() {
-                            v[i].clone()
-                        } else {
-                            Value::Null
-                        }
-                    }
-                    _ => Value::Null,
-                }
+        if array.data_type() != DataType::Array {
+            return Ok(Node::null());
+        }
+        let index = self.bound(&nodes[1], &array)?.unwrap_or_default();
+        Ok(array.at(index))
+    }
+    /// Evaluates an index or slice bound against the array being indexed.
+    fn bound(&self, ast: &AST, value: &Node<'j, J>) -> Result<Option<isize>, RuntimeError> {
+        let bound = match &ast.node_type {
+            NodeType::None => return Ok(None),
+            NodeType::Number(number) => return Ok(Some(*number as isize)),
+            _ => self.visit(ast, value)?.into_value(),
+        };
+        let Some(number) = bound.as_number() else {
+            return Err(RuntimeError::get_invalid_type_error_builder()
+                .at(ast.position)
+                .for_parameter("index or slice bound")
+                .expected_data_types(&vec![DataType::Number])
+                .received(&bound)
+                .build());
+        };
+        let index = match number.as_i128() {
+            // bounds beyond the array are clamped anyway
+            Some(integer) => Some(integer.clamp(isize::MIN as i128, isize::MAX as i128) as isize),
+            None => {
+                let float = number.as_f64();
+                (float.fract() == 0.0).then_some(float as isize)
             }
-            _ => Value::Null,
-        })
+        };
+        let Some(index) = index else {
+            return Err(RuntimeError::get_invalid_value_error_builder()
+                .at(ast.position)
+                .for_parameter("index or slice bound")
+                .received(&bound)
+                .expected("an integer")
+                .build());
+        };
+        Ok(Some(index))
     }
 
     fn visit_json_value(&self, node: &str, position: &Position) -> ReturnValue {
         Value::from_json(node).map_err(|e| Self::map_err(e, *position))
     }
 
-    fn visit_arithmetic_expression(&self, nodes: &Vec<AST>, value: &Value) -> ReturnValue {
+    fn visit_arithmetic_expression(&self, nodes: &Vec<AST>, value: &Node<'j, J>) -> ReturnValue {
         if let NodeType::None = nodes[0].node_type {
             return self.visit_arithmetic_unary(nodes, value);
         }
         self.visit_arithmetic_binary(nodes, value)
     }
-    fn visit_arithmetic_unary(&self, nodes: &Vec<AST>, value: &Value) -> ReturnValue {
-        let right = self.visit(&nodes[2], value)?;
+    fn visit_arithmetic_unary(&self, nodes: &Vec<AST>, value: &Node<'j, J>) -> ReturnValue {
+        let right = self.visit(&nodes[2], value)?.into_value();
         let rhs = right.as_f64();
 
         if let None = rhs {
@@ -380,9 +500,10 @@ impl<'a> Interpreter<'a> {
                 .for_reason(&reason)
                 .build());
         }
-        let result = match &nodes[1].node_type {
-            NodeType::Minus => Value::from_f64(-rhs.unwrap()),
-            NodeType::Plus => Ok(right.clone()),
+        let result = match (&nodes[1].node_type, right.as_number().unwrap().as_i128()) {
+            (NodeType::Minus, Some(integer)) => Ok(Number::from_i128(-integer).into()),
+            (NodeType::Minus, None) => Value::from_f64(-rhs.unwrap()),
+            (NodeType::Plus, _) => Ok(right.clone()),
 
             _ => unreachable!(),
         };
@@ -396,10 +517,10 @@ impl<'a> Interpreter<'a> {
             ok => ok,
         }
     }
-    fn visit_arithmetic_binary(&self, nodes: &Vec<AST>, value: &Value) -> ReturnValue {
+    fn visit_arithmetic_binary(&self, nodes: &Vec<AST>, value: &Node<'j, J>) -> ReturnValue {
         assert_eq!(3, nodes.len());
-        let left = self.visit(&nodes[0], value)?;
-        let right = self.visit(&nodes[2], value)?;
+        let left = self.visit(&nodes[0], value)?.into_value();
+        let right = self.visit(&nodes[2], value)?.into_value();
 
         let lhs = left.as_f64();
         let rhs = right.as_f64();
@@ -427,6 +548,16 @@ impl<'a> Interpreter<'a> {
                 .build());
         }
 
+        let integers = (
+            left.as_number().unwrap().as_i128(),
+            right.as_number().unwrap().as_i128(),
+        );
+        if let (Some(lhs), Some(rhs)) = integers
+            && let Some(result) = Self::integer_arithmetic(&nodes[1].node_type, lhs, rhs)
+        {
+            return Ok(Number::from_i128(result).into());
+        }
+
         let result = match &nodes[1].node_type {
             NodeType::Divide => Value::from_f64(lhs.unwrap() / rhs.unwrap()),
             NodeType::Minus => Value::from_f64(lhs.unwrap() - rhs.unwrap()),
@@ -449,54 +580,83 @@ impl<'a> Interpreter<'a> {
         }
     }
 
-    fn visit_comparator_expression(&self, nodes: &Vec<AST>, value: &Value) -> ReturnValue {
+    /// Evaluates an arithmetic operation on two integers exactly.
+    ///
+    /// Returns `None` when the result must be computed as a float instead:
+    /// for `/`, which always yields a float, on overflow, or on a zero divisor.
+    fn integer_arithmetic(operator: &NodeType, lhs: i128, rhs: i128) -> Option<i128> {
+        match operator {
+            NodeType::Minus => lhs.checked_sub(rhs),
+            NodeType::Multiply => lhs.checked_mul(rhs),
+            NodeType::Plus => lhs.checked_add(rhs),
+            NodeType::Modulo => lhs.checked_rem(rhs),
+            NodeType::Div => lhs.checked_div(rhs),
+            _ => None,
+        }
+    }
+
+    fn visit_comparator_expression(&self, nodes: &Vec<AST>, value: &Node<'j, J>) -> ReturnValue {
         assert_eq!(3, nodes.len());
-        let left = self.visit(&nodes[0], value)?;
-        let right = self.visit(&nodes[2], value)?;
+        let left = self.visit(&nodes[0], value)?.into_value();
+        let right = self.visit(&nodes[2], value)?.into_value();
 
-        let requires_numbers = match &nodes[1].node_type {
-            NodeType::Equal => false,
-            NodeType::NotEqual => false,
-            _ => true,
+        let ordering = match &nodes[1].node_type {
+            NodeType::Equal => return Ok((left == right).into()),
+            NodeType::NotEqual => return Ok((left != right).into()),
+            _ => Self::compare(&left, &right),
         };
 
-        let lhs = left.as_f64();
-        let rhs = right.as_f64();
-
-        if let None = lhs {
-            if requires_numbers {
-                return Ok(Value::Null);
-            }
-        }
-        if let None = rhs {
-            if requires_numbers {
-                return Ok(Value::Null);
-            }
-        }
+        // ordering comparisons on mismatched types evaluate to null
+        let ordering = match ordering {
+            Some(ordering) => ordering,
+            None => return Ok(Value::Null),
+        };
         let compared: bool = match &nodes[1].node_type {
-            NodeType::GreaterThan => lhs.unwrap() > rhs.unwrap(),
-            NodeType::GreaterThanOrEqual => lhs.unwrap() >= rhs.unwrap(),
-            NodeType::LessThanOrEqual => lhs.unwrap() <= rhs.unwrap(),
-            NodeType::LessThan => lhs.unwrap() < rhs.unwrap(),
-            NodeType::Equal => left == right,
-            NodeType::NotEqual => left != right,
+            NodeType::GreaterThan => ordering.is_gt(),
+            NodeType::GreaterThanOrEqual => ordering.is_ge(),
+            NodeType::LessThanOrEqual => ordering.is_le(),
+            NodeType::LessThan => ordering.is_lt(),
             _ => unreachable!(),
         };
 
         Ok(compared.into())
     }
 
-    fn visit_logical_expression(&self, nodes: &Vec<AST>, value: &Value) -> ReturnValue {
+    /// Compares two numbers, or two strings lexicographically by code point.
+    fn compare(left: &Value, right: &Value) -> Option<std::cmp::Ordering> {
+        match (left, right) {
+            // byte-wise ordering of UTF-8 strings is the same as code point ordering
+            (Value::String(lhs), Value::String(rhs)) => Some(lhs.cmp(rhs)),
+            (Value::Number(lhs), Value::Number(rhs)) => Some(lhs.cmp(rhs)),
+            _ => None,
+        }
+    }
+
+    fn visit_conditional_expression(
+        &self,
+        nodes: &[AST],
+        value: &Node<'j, J>,
+    ) -> NodeResult<'j, J> {
+        assert_eq!(3, nodes.len());
+        let condition = self.visit(&nodes[0], value)?;
+        if condition.is_truthy() {
+            self.visit(&nodes[1], value)
+        } else {
+            self.visit(&nodes[2], value)
+        }
+    }
+
+    fn visit_logical_expression(&self, nodes: &Vec<AST>, value: &Node<'j, J>) -> NodeResult<'j, J> {
         if nodes.len() == 2 {
             return self
                 .visit(&nodes[1], value)
-                .map(|x| Value::Boolean(!x.is_truthy()));
+                .map(|x| Node::Owned(Value::Boolean(!x.is_truthy())));
         }
 
         let left = self.visit(&nodes[0], value)?;
         let right = self.visit(&nodes[2], value)?;
 
-        let compared: Value = match &nodes[1].node_type {
+        let compared = match &nodes[1].node_type {
             NodeType::And => {
                 if left.is_truthy() {
                     right
@@ -514,51 +674,131 @@ impl<'a> Interpreter<'a> {
             _ => unreachable!(),
         };
 
-        Ok(compared.into())
+        Ok(compared)
     }
 
-    fn visit_function_expression(&self, ast: &Vec<AST>, value: &Value) -> ReturnValue {
+    fn visit_function_expression(&self, ast: &Vec<AST>, value: &Node<'j, J>) -> ReturnValue {
         let function_name = ast[0].function_name();
         let function_args = ast[1].function_arguments();
 
         let mut arguments = Vec::new();
         for function_arg in function_args {
-            arguments.push(self.visit(function_arg, value)?);
+            arguments.push(self.visit(function_arg, value)?.into_value());
         }
         self.runtime.call(function_name, &arguments, self)
     }
 
-    fn visit_let_expression(&self, ast: &Vec<AST>, value: &Value) -> ReturnValue {
+    fn visit_let_expression(&self, ast: &Vec<AST>, value: &Node<'j, J>) -> NodeResult<'j, J> {
         let mut scopes: Map<String, Value> = Map::new();
-        let bindings = ast[0].bindings();
+        let (bindings, sequential) = match &ast[0].node_type {
+            NodeType::SequentialLetBindings(bindings) => (bindings, true),
+            _ => (ast[0].bindings(), false),
+        };
         for index in 0..bindings.len() {
             if index % 2 != 0 {
                 continue;
             }
-            let name = bindings[index].variable_ref();
-            let value = self.visit(&bindings[index + 1], value)?;
-            scopes.insert(name.to_string(), value);
+            // with let* each binding sees the preceding ones
+            let value = if sequential {
+                self.with_scope(&scopes)
+                    .visit(&bindings[index + 1], value)?
+            } else {
+                self.visit(&bindings[index + 1], value)?
+            };
+            let value = value.into_value();
+            Self::destructure(&bindings[index], value, &mut scopes)?;
         }
 
         self.with_scope(&scopes).visit(&ast[1], value)
     }
+    fn destructure(
+        pattern: &AST,
+        value: Value,
+        scopes: &mut Map<String, Value>,
+    ) -> Result<(), RuntimeError> {
+        // missing elements or keys are bound to null
+        match &pattern.node_type {
+            NodeType::ArrayPattern(patterns) => {
+                let mut elements = match value {
+                    Value::Array(elements) => elements.into_iter(),
+                    _ => Vec::new().into_iter(),
+                };
+                for pattern in patterns {
+                    let element = elements.next().unwrap_or(Value::Null);
+                    Self::destructure(pattern, element, scopes)?;
+                }
+            }
+            NodeType::ObjectPattern(patterns) => {
+                for pair in patterns.chunks(2) {
+                    let key = match &pair[0].node_type {
+                        NodeType::QuotedIdentifier(key) => {
+                            Self::unwrap_quoted_identifier(key, pair[0].position)?
+                        }
+                        _ => pair[0].identifier().clone(),
+                    };
+                    let member = match &value {
+                        Value::Object(object) => object.get(&key).cloned(),
+                        _ => None,
+                    };
+                    Self::destructure(&pair[1], member.unwrap_or(Value::Null), scopes)?;
+                }
+            }
+            _ => {
+                scopes.insert(pattern.variable_ref().to_string(), value);
+            }
+        }
+        Ok(())
+    }
 
-    fn visit(&self, ast: &AST, value: &Value) -> ReturnValue {
+    fn visit(&self, ast: &AST, value: &Node<'j, J>) -> NodeResult<'j, J> {
+        let shared = self.shared.filter(|_| self.is_root(value));
+        if let Some(node) = shared.and_then(|shared| shared.get(ast)) {
+            return Ok(node);
+        }
+        let result = self
+            .visit_node(ast, value)
+            .map_err(|err| Self::locate(err, ast));
+        if let (Some(shared), Ok(node)) = (shared, &result) {
+            shared.insert(ast, node);
+        }
+        result
+    }
+    fn is_root(&self, value: &Node<'j, J>) -> bool {
+        match (value, self.root) {
+            (Node::Borrowed(node, _), Some(root)) => std::ptr::eq(*node, root),
+            _ => false,
+        }
+    }
+    fn visit_node(&self, ast: &AST, value: &Node<'j, J>) -> NodeResult<'j, J> {
         match &ast.node_type {
-            NodeType::ArithmeticExpression(nodes) => self.visit_arithmetic_expression(nodes, value),
-            NodeType::ComparatorExpression(nodes) => self.visit_comparator_expression(nodes, value),
+            NodeType::ArithmeticExpression(nodes) => self
+                .visit_arithmetic_expression(nodes, value)
+                .map(Node::Owned),
+            NodeType::ComparatorExpression(nodes) => self
+                .visit_comparator_expression(nodes, value)
+                .map(Node::Owned),
+            NodeType::ConditionalExpression(nodes) => {
+                self.visit_conditional_expression(nodes, value)
+            }
             NodeType::CurrentNode => self.visit_current_node(ast, value),
             NodeType::Expression(nodes) => self.visit_expref(nodes, value),
-            NodeType::FunctionExpression(nodes) => self.visit_function_expression(nodes, value),
+            NodeType::FunctionExpression(nodes) => self
+                .visit_function_expression(nodes, value)
+                .map(Node::Owned),
+            NodeType::DescendantProjection(nodes) => self.visit_descendant_projection(nodes, value),
             NodeType::HashWildcardProjection(nodes) => {
                 self.visit_hash_wildcard_projection(nodes, value)
             }
             NodeType::IndexExpression(nodes) => self.visit_index_expression(nodes, value),
-            NodeType::JsonValue(json) => self.visit_json_value(json, &ast.position),
+            NodeType::JsonValue(json) => {
+                self.visit_json_value(json, &ast.position).map(Node::Owned)
+            }
+            NodeType::LambdaExpression(_) => Ok(Node::Owned(Value::Expression(ast.clone()))),
             NodeType::LetExpression(nodes) => self.visit_let_expression(nodes, value),
             NodeType::LogicalExpression(nodes) => self.visit_logical_expression(nodes, value),
             NodeType::MultiSelectHash(nodes) => self.visit_multi_select_hash(nodes, value),
             NodeType::MultiSelectList(nodes) => self.visit_multi_select_list(nodes, value),
+            NodeType::Placeholder(_) => self.visit_placeholder(ast, value),
             NodeType::ParenExpression(nodes) => self.visit(&nodes[0], value),
             NodeType::PipeExpression(nodes) => self.visit_pipe_expression(nodes, value),
             NodeType::Projection(nodes) => self.visit_projection(nodes, value),
@@ -577,9 +817,6 @@ impl<'a> Interpreter<'a> {
             }
         }
     }
-    fn eval(&self, ast: &AST, root: &Value) -> ReturnValue {
-        self.visit(ast, root)
-    }
     fn unwrap_quoted_identifier(
         quoted_string: &String,
         position: Position,
@@ -592,6 +829,17 @@ impl<'a> Interpreter<'a> {
             .to_string();
         Ok(identifier)
     }
+    /// Attaches the location of the specified node to an error
+    /// that has been raised without one, _e.g_ by [`Runtime::call`].
+    fn locate(mut error: RuntimeError, ast: &AST) -> RuntimeError {
+        if error.position.is_none() {
+            error.position = Some(ast.position);
+        }
+        if error.span.is_none() && error.position == Some(ast.position) && !ast.span.is_empty() {
+            error.span = Some(ast.span);
+        }
+        error
+    }
     fn map_err(error: serde_json::Error, position: Position) -> RuntimeError {
         let message = format!("{}", error);
         RuntimeError::get_syntax_error_builder()
@@ -600,7 +848,7 @@ impl<'a> Interpreter<'a> {
             .build()
     }
 }
-impl<'a> FunctionContext for Interpreter<'a> {
+impl<'a, 'j, J: JsonLike> FunctionContext for Interpreter<'a, 'j, J> {
     fn create_by_function<'b>(
         &'b self,
         ast: &'b AST,
@@ -609,7 +857,7 @@ impl<'a> FunctionContext for Interpreter<'a> {
         param_index: usize,
     ) -> Result<ByFunctionHolder<'b>, RuntimeError> {
         let closure = move |value: &Value| -> Result<Value, RuntimeError> {
-            let result = self.eval(ast, value);
+            let result = self.invoke_expression(ast, std::slice::from_ref(value));
             if result.is_err() {
                 return result;
             }
@@ -638,13 +886,45 @@ impl<'a> FunctionContext for Interpreter<'a> {
             closure: Box::new(closure),
         })
     }
+    fn invoke(&self, ast: &AST, args: &[Value]) -> ReturnValue {
+        self.invoke_expression(ast, args)
+    }
+    fn invoke_with_bindings(
+        &self,
+        ast: &AST,
+        args: &[Value],
+        bindings: &Map<String, Value>,
+    ) -> ReturnValue {
+        self.with_scope(bindings).invoke_expression(ast, args)
+    }
+    fn evaluate_with_bindings(&self, ast: &AST, bindings: &Map<String, Value>) -> ReturnValue {
+        if self.depth >= MAX_CALL_DEPTH {
+            let reason = format!(
+                "function calls exceeded the maximum depth of {}, possibly due to infinite recursion",
+                MAX_CALL_DEPTH
+            );
+            return Err(RuntimeError::get_syntax_error_builder()
+                .set_kind(Kind::InvalidValue)
+                .for_reason(&reason)
+                .build());
+        }
+        let interpreter = Interpreter {
+            root: self.root,
+            runtime: self.runtime,
+            scopes: Some(Box::new(Scopes::new(bindings))),
+            depth: self.depth + 1,
+            track_paths: self.track_paths,
+            shared: None,
+        };
+        interpreter.visit(ast, &Node::null()).map(Node::into_value)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::Value;
-    use crate::errors::{Kind, Position};
+    use crate::errors::{Details, Kind, Position};
     use crate::functions::ReturnValue;
     use crate::parser::Slice;
     use crate::{NodeType, map};
@@ -794,10 +1074,7 @@ mod tests {
 
     #[rstest]
     #[case(r#"{"foo": "bar"}"#, "null")]
-    #[case(
-        r#"{"foo": [1, [2, [3], [4, 5], 6]]}"#,
-        "[1.0,2.0,[3.0],[4.0,5.0],6.0]"
-    )]
+    #[case(r#"{"foo": [1, [2, [3], [4, 5], 6]]}"#, "[1,2,[3],[4,5],6]")]
     fn flatten_projection(#[case] input: &str, #[case] expected: &str) {
         let nodes = vec![
             make_ast(NodeType::Flatten),
@@ -852,7 +1129,7 @@ mod tests {
 
     #[rstest]
     #[case(r#"{"foo": "bar"}"#, "null")]
-    #[case(r#"{"foo": [1, 2, 3]}"#, "[1.0,2.0,3.0]")]
+    #[case(r#"{"foo": [1, 2, 3]}"#, "[1,2,3]")]
     fn list_wildcard_projection(#[case] input: &str, #[case] expected: &str) {
         let nodes = vec![
             make_ast(NodeType::ListWildcard),
@@ -907,7 +1184,7 @@ mod tests {
 
         let root = from_json(r#"[1, 2, 3]"#);
         let result = setup(&root, &ast).unwrap();
-        assert_eq!(r#"[3.0,1.0]"#, result.to_json());
+        assert_eq!(r#"[3,1]"#, result.to_json());
     }
 
     #[test]
@@ -1023,12 +1300,12 @@ mod tests {
     }
 
     #[rstest]
-    #[case(make_ast(NodeType::Plus), "23.0")]
-    #[case(make_ast(NodeType::Minus), "19.0")]
-    #[case(make_ast(NodeType::Multiply), "42.0")]
+    #[case(make_ast(NodeType::Plus), "23")]
+    #[case(make_ast(NodeType::Minus), "19")]
+    #[case(make_ast(NodeType::Multiply), "42")]
     #[case(make_ast(NodeType::Divide), "10.5")]
-    #[case(make_ast(NodeType::Div), "10.0")]
-    #[case(make_ast(NodeType::Modulo), "1.0")]
+    #[case(make_ast(NodeType::Div), "10")]
+    #[case(make_ast(NodeType::Modulo), "1")]
     fn arithmetic_expression(#[case] op: AST, #[case] expected: &str) {
         let nodes = vec![
             make_ast(NodeType::UnquotedIdentifier("foo".to_string())),
@@ -1043,8 +1320,8 @@ mod tests {
     }
 
     #[rstest]
-    #[case(make_ast(NodeType::Plus), "42.0")]
-    #[case(make_ast(NodeType::Minus), "-42.0")]
+    #[case(make_ast(NodeType::Plus), "42")]
+    #[case(make_ast(NodeType::Minus), "-42")]
     fn arithmetic_unary(#[case] op: AST, #[case] expected: &str) {
         let nodes = vec![
             make_ast(NodeType::None),
@@ -1058,6 +1335,40 @@ mod tests {
         assert_eq!(expected, result.to_json());
     }
 
+    #[rstest]
+    #[case("id + `1`", "9007199254740993")]
+    #[case("id - `1`", "9007199254740991")]
+    #[case("id * `2`", "18014398509481984")]
+    #[case("id // `2`", "4503599627370496")]
+    #[case("id % `10`", "2")]
+    #[case("-id", "-9007199254740992")]
+    #[case("id / `2`", "4503599627370496.0")]
+    #[case("`1.5` + `1`", "2.5")]
+    fn arithmetic_integers(#[case] expression: &str, #[case] expected: &str) {
+        let root = from_json(r#"{"id": 9007199254740992}"#);
+        let result = crate::search(expression, &root).unwrap();
+        assert_eq!(expected, result.to_json(), "{}", expression);
+    }
+
+    #[test]
+    fn arithmetic_integers_overflow_to_float() {
+        let root = from_json(r#"{"big": 18446744073709551615}"#);
+        let result = crate::search("big + `1`", &root).unwrap();
+        let number = result.as_number().unwrap();
+        assert!(!number.is_integer());
+        assert_eq!(18446744073709551616.0, number.as_f64());
+    }
+
+    #[rstest]
+    #[case("id == `9007199254740993`", false)]
+    #[case("id < `9007199254740993`", true)]
+    #[case("id == `9007199254740992.0`", true)]
+    fn compare_integers(#[case] expression: &str, #[case] expected: bool) {
+        let root = from_json(r#"{"id": 9007199254740992}"#);
+        let result = crate::search(expression, &root).unwrap();
+        assert_eq!(expected, result, "{}", expression);
+    }
+
     #[rstest]
     #[case(make_ast(NodeType::Plus), Value::from_f64(23.0).unwrap(), "not_a_number".into())]
     #[case(make_ast(NodeType::Minus), Value::from_f64(23.0).unwrap(), "not_a_number".into())]
@@ -1140,6 +1451,224 @@ mod tests {
         assert_eq!(Value::Null, result);
     }
 
+    #[rstest]
+    #[case(r#""abc" < "abd""#, true)]
+    #[case(r#""abc" < "ab""#, false)]
+    #[case(r#""" < "a""#, true)]
+    #[case(r#""B" < "a""#, true)]
+    #[case(r#""a" <= "a""#, true)]
+    #[case(r#""b" > "a""#, true)]
+    #[case(r#""a" >= "b""#, false)]
+    #[case(r#""é" > "z""#, true)]
+    #[case("\"\u{ffff}\" < \"\u{1f600}\"", true)]
+    #[case(r#""2024-02-01" >= "2024-01-31""#, true)]
+    fn comparator_expression_strings(#[case] comparison: &str, #[case] expected: bool) {
+        let expression = comparison.replace('"', "'");
+        let result = crate::search(&expression, &Value::Null).unwrap();
+        assert_eq!(Value::Boolean(expected), result, "{}", comparison);
+    }
+
+    #[rstest]
+    #[case("[?date >= '2024-01-01'].id", r#"["b","c"]"#)]
+    #[case("[?date < '2024-01-01'].id", r#"["a"]"#)]
+    #[case("[?id > `1`].id", "[]")]
+    #[case("[?date > `null`].id", "[]")]
+    #[case("[?tags > `[]`].id", "[]")]
+    #[case("[?id < `true`].id", "[]")]
+    #[case(
+        "['a' < `1`, `1` < 'a', 'a' < `[]`, `{}` < 'a', 'a' <= `null`]",
+        "[null,null,null,null,null]"
+    )]
+    fn comparator_expression_mixed_types(#[case] expression: &str, #[case] expected: &str) {
+        let given = from_json(
+            r#"[
+                {"id": "a", "date": "2023-12-31", "tags": []},
+                {"id": "b", "date": "2024-01-01", "tags": ["x"]},
+                {"id": "c", "date": "2024-06-30T12:00:00Z", "tags": ["y"]}
+            ]"#,
+        );
+        let result = crate::search(expression, &given).unwrap();
+        assert_eq!(from_json(expected), result);
+    }
+
+    #[rstest]
+    #[case("let $i = `1` in items[$i]", "2")]
+    #[case("let $i = `-1` in items[$i]", "5")]
+    #[case("let $i = `9` in items[$i]", "null")]
+    #[case("items[(length(@) - `1`)]", "5")]
+    #[case("items[(`4` // `2`)]", "3")]
+    #[case("let $n = `2` in items[:$n]", "[1,2]")]
+    #[case("let $n = `2` in items[$n:]", "[3,4,5]")]
+    #[case("let $n = `-2` in items[::$n]", "[5,3,1]")]
+    #[case("items[(length(@) - `2`):]", "[4,5]")]
+    #[case("let $n = `2` in name[:$n]", r#""ab""#)]
+    #[case("let $i = `1` in missing[$i]", "null")]
+    #[case("let $i = 'a' in missing[$i:]", "null")]
+    #[case("let $i = `-9223372036854775807` in items[$i:]", "[1,2,3,4,5]")]
+    #[case("let $i = `-9223372036854775807` in items[:$i]", "[]")]
+    #[case("let $n = `9223372036854775807` in items[:$n]", "[1,2,3,4,5]")]
+    #[case("let $n = `9223372036854775807` in items[$n:]", "[]")]
+    #[case("let $n = `9223372036854775807` in items[$n::-1]", "[5,4,3,2,1]")]
+    #[case("let $n = `-9223372036854775807` in items[:$n:-1]", "[5,4,3,2,1]")]
+    #[case("let $n = `9223372036854775807` in items[::$n]", "[1]")]
+    #[case("let $n = `-9223372036854775807` in items[::$n]", "[5]")]
+    #[case("let $n = `9223372036854775807` in name[:$n]", r#""abcde""#)]
+    #[case("let $n = `18446744073709551615` in items[:$n]", "[1,2,3,4,5]")]
+    #[case("let $n = `-18446744073709551615` in items[$n:]", "[1,2,3,4,5]")]
+    #[case("let $i = `18446744073709551615` in items[$i]", "null")]
+    #[case("let $n = `2.0` in items[:$n]", "[1,2]")]
+    #[case("let $i = `-9223372036854775807` in items[$i]", "null")]
+    #[case("let $n = `-1` in empty[::$n]", "[]")]
+    fn dynamic_bounds(#[case] expression: &str, #[case] expected: &str) {
+        let given = from_json(r#"{"items": [1, 2, 3, 4, 5], "name": "abcde", "empty": []}"#);
+        let result = crate::search(expression, &given).unwrap();
+        assert_eq!(from_json(expected), result, "{}", expression);
+    }
+
+    #[rstest]
+    #[case("let $i = 'a' in items[$i]", Kind::InvalidType, (1, 23))]
+    #[case("let $i = `null` in items[:$i]", Kind::InvalidType, (1, 27))]
+    #[case("items[(`1.5`)]", Kind::InvalidValue, (1, 7))]
+    #[case("let $n = `0.5` in items[::$n]", Kind::InvalidValue, (1, 27))]
+    #[case("let $n = `0` in items[::$n]", Kind::InvalidValue, (1, 22))]
+    fn dynamic_bounds_err(
+        #[case] expression: &str,
+        #[case] kind: Kind,
+        #[case] position: (usize, usize),
+    ) {
+        let given = from_json(r#"{"items": [1, 2, 3]}"#);
+        let err = crate::search(expression, &given).unwrap_err();
+        assert_eq!(kind, err.kind, "{}", expression);
+        assert_eq!(
+            Some(Position::new(position.0, position.1)),
+            err.position,
+            "{}",
+            expression
+        );
+    }
+
+    #[test]
+    fn dynamic_bounds_err_details() {
+        let given = from_json(r#"{"items": [1, 2, 3]}"#);
+        let err = crate::search("items[(`1.5`)]", &given).unwrap_err();
+        assert!(
+            matches!(err.details.as_deref(), Some(Details::InvalidValue { expected, .. }) if expected == "an integer"),
+            "{:?}",
+            err.details
+        );
+        let err = crate::search("items[('a')]", &given).unwrap_err();
+        assert!(
+            matches!(err.details.as_deref(), Some(Details::InvalidType { expected, .. }) if *expected == vec![DataType::Number]),
+            "{:?}",
+            err.details
+        );
+    }
+
+    #[rstest]
+    #[case("..id", "[1,2,3,4]")]
+    #[case("items..id", "[2,3,4]")]
+    #[case("items[1]..id", "[3,4]")]
+    #[case("..name.first", r#"["a","b"]"#)]
+    #[case("..tags[0]", r#"["x","y"]"#)]
+    #[case("..tags[*]", r#"[["x"],["y","z"]]"#)]
+    #[case("..items[?id > `2`].id", "[[3]]")]
+    #[case("..id | [-1]", "4")]
+    #[case("..missing", "[]")]
+    #[case("id..id", "null")]
+    #[case("unknown..id", "null")]
+    #[case("items[0]..{id: id}", r#"[{"id":2},{"id":null},{"id":null}]"#)]
+    fn descendant_projection(#[case] expression: &str, #[case] expected: &str) {
+        let given = from_json(
+            r#"{"id": 1, "items": [
+                {"id": 2, "name": {"first": "a"}, "tags": ["x"]},
+                {"id": 3, "name": {"first": "b"}, "children": [{"id": 4}], "tags": ["y", "z"]}
+            ]}"#,
+        );
+        let result = crate::search(expression, &given).unwrap();
+        assert_eq!(from_json(expected), result, "{}", expression);
+    }
+
+    #[rstest]
+    #[case("foo ? 'yes' : 'no'", "\"yes\"")]
+    #[case("bar ? 'yes' : 'no'", "\"no\"")]
+    #[case("empty ? 'yes' : 'no'", "\"no\"")]
+    #[case("missing ? 'yes' : 'no'", "\"no\"")]
+    #[case("foo ? empty : 'no'", "[]")]
+    #[case("(foo && empty) || 'no'", "\"no\"")]
+    #[case("bar ? 'a' : foo ? 'b' : 'c'", "\"b\"")]
+    #[case("items[?value > `1` ? flag : !flag].value", "[0,2]")]
+    #[case(
+        "{a: foo ? 'x' : 'y', b: [bar ? 'x' : 'y']}",
+        r#"{"a": "x", "b": ["y"]}"#
+    )]
+    #[case("foo ? items[*].value:bar", "[0,1,2]")]
+    #[case("bar ? foo:items[0].value", "0")]
+    #[case("{a:foo, b:bar ? empty:items[0].flag}", r#"{"a": true, "b": false}"#)]
+    fn conditional_expression(#[case] expression: &str, #[case] expected: &str) {
+        let given = from_json(
+            r#"{"foo": true, "bar": false, "empty": [], "items": [
+                {"value": 0, "flag": false}, {"value": 1, "flag": true}, {"value": 2, "flag": true}
+            ]}"#,
+        );
+        let result = crate::search(expression, &given).unwrap();
+        assert_eq!(from_json(expected), result);
+    }
+
+    #[test]
+    fn conditional_expression_evaluates_one_branch() {
+        let result = crate::search("`true` ? 'ok' : unknown()", &Value::Null).unwrap();
+        assert_eq!("ok", result);
+    }
+
+    #[rstest]
+    #[case("min_by(people, ($p) => $p.age).name", r#""Bob""#)]
+    #[case("min_by(people, ($p) => @.age).name", r#""Bob""#)]
+    #[case(
+        "let $base = `50` in min_by(people, ($p) => $base - $p.age).name",
+        r#""Alice""#
+    )]
+    #[case("min_by(people, () => `0`).name", r#""Alice""#)]
+    #[case("min_by(people, ($p) => $p.age * `-1`).name", r#""Alice""#)]
+    #[case("min_by(people, ($p) => $p.tags[*].id | length(@)).name", r#""Bob""#)]
+    #[case(
+        "min_by(people, ($p) => $p.tags | length(@) * `-1`).name",
+        r#""Alice""#
+    )]
+    #[case("min_by(people, ($p) => $p.tags[0] | id || `9`).name", r#""Alice""#)]
+    fn lambda_expression(#[case] expression: &str, #[case] expected: &str) {
+        let root = from_json(
+            r#"{"people": [
+                {"name": "Alice", "age": 42, "tags": [{"id": 1}, {"id": 2}]},
+                {"name": "Bob", "age": 35, "tags": []}
+            ]}"#,
+        );
+        let result = crate::search(expression, &root).unwrap();
+        assert_eq!(from_json(expected), result);
+    }
+
+    #[rstest]
+    #[case(&["1", "2"], "[1, 2]")]
+    #[case(&["1"], "[1, null]")]
+    #[case(&["1", "2", "3"], "[1, 2]")]
+    fn invoke_lambda_expression(#[case] args: &[&str], #[case] expected: &str) {
+        let ast = crate::parse("f(($x, $y) => [$x, $y])").unwrap();
+        let ast = match &ast.node_type {
+            NodeType::FunctionExpression(nodes) => nodes[1].clone(),
+            _ => unreachable!(),
+        };
+        let ast = match &ast.node_type {
+            NodeType::FunctionArguments(args) => args[0].clone(),
+            _ => unreachable!(),
+        };
+        let args: Vec<Value> = args.iter().map(|arg| from_json(arg)).collect();
+        let runtime = Runtime::get_shared_runtime();
+        let root = Value::Null;
+        let result = Interpreter::new(&runtime, &root)
+            .invoke_expression(&ast, &args)
+            .unwrap();
+        assert_eq!(from_json(expected), result);
+    }
+
     #[rstest]
     #[case(make_ast(NodeType::And), Value::Null)]
     #[case(make_ast(NodeType::Or), Value::from_f64(21.0).unwrap())]
@@ -1234,6 +1763,56 @@ mod tests {
         assert_eq!(expected, result);
     }
 
+    #[rstest]
+    #[case("let* $a = foo, $b = $a.bar in $b", "1")]
+    #[case("let* $a = foo, $a = $a.bar in $a", "1")]
+    #[case("let $a = `1` in let* $b = $a, $a = `2` in [$a, $b]", "[2, 1]")]
+    #[case("let [$a, $b] = list in [$b, $a]", "[2, 1]")]
+    #[case("let [$a, $b, $c] = list in $c", "null")]
+    #[case("let [$a] = foo in $a", "null")]
+    #[case("let {bar: $b} = foo in $b", "1")]
+    #[case(r#"let {"bar": $b, baz: $z} = foo in [$b, $z]"#, "[1, null]")]
+    #[case("let {bar: $b} = list in $b", "null")]
+    #[case(
+        "let {foo: {bar: $b}, list: [$x, $y]} = @ in [$b, $x, $y]",
+        "[1, 1, 2]"
+    )]
+    #[case("let* [$a, $b] = list, $c = $a + $b in $c", "3")]
+    fn let_expression_destructuring(#[case] expression: &str, #[case] expected: &str) {
+        let root = from_json(r#"{"foo": {"bar": 1}, "list": [1, 2]}"#);
+        let result = crate::search(expression, &root).unwrap();
+        assert_eq!(from_json(expected), result, "{}", expression);
+    }
+
+    #[test]
+    fn let_expression_bindings_are_not_sequential() {
+        let err = crate::search("let $a = `1`, $b = $a in $b", &Value::Null).unwrap_err();
+        assert_eq!(Kind::UndefinedVariable, err.kind);
+    }
+
+    #[rstest]
+    #[case(Kind::UnknownFunction, (1, 7), (6, 16), "foo | unknown(@)")]
+    #[case(Kind::InvalidArity, (1, 7), (6, 21), "foo | length(@, @, @)")]
+    #[case(Kind::InvalidType, (1, 7), (6, 12), "foo | abs(@)")]
+    #[case(Kind::InvalidType, (1, 13), (12, 25), "foo | abs(  min_by(@, &@) )")]
+    #[case(Kind::InvalidType, (1, 14), (13, 30), "[`1`, `2`] | min_by(@, &'age')")]
+    #[case(Kind::Syntax, (1, 11), (0, 15), "length(@) + foo")]
+    #[case(Kind::UndefinedVariable, (1, 7), (6, 10), "foo | $bar")]
+    fn runtime_error_position(
+        #[case] kind: Kind,
+        #[case] position: (usize, usize),
+        #[case] span: (usize, usize),
+        #[case] expression: &str,
+    ) {
+        let runtime = Runtime::get_shared_runtime();
+        let root = from_json(r#"{"foo": "bar"}"#);
+        let err = runtime.search(expression, &root).unwrap_err();
+        assert_eq!(kind, err.kind);
+        assert_eq!(Some(Position::new(position.0, position.1)), err.position);
+        let err_span = err.span.unwrap();
+        assert_eq!(span, (err_span.start, err_span.end));
+    }
+
     #[test]
     fn let_expression_undefined_variable() {
         let ast = make_ast(NodeType::VariableRef("$baz".to_string()));
@@ -1247,4 +1826,57 @@ mod tests {
             _ => assert!(false),
         }
     }
+
+    #[rstest]
+    #[case("people[0].name", vec![(Some("/people/0/name"), r#""Ada""#)])]
+    #[case("people[?age > `30`].name", vec![(Some("/people/0/name"), r#""Ada""#), (Some("/people/2/name"), r#""Grace""#)])]
+    #[case("people[-1:].tags[]", vec![(Some("/people/2/tags/0"), r#""b""#), (Some("/people/2/tags/1"), r#""c""#)])]
+    #[case("people[*].tags[0]", vec![(Some("/people/0/tags/0"), r#""a""#), (Some("/people/2/tags/0"), r#""b""#)])]
+    #[case("people[1].*", vec![(Some("/people/1/name"), r#""Alan""#), (Some("/people/1/age"), "12")])]
+    #[case("people | [1] | $.owner", vec![(Some("/owner"), r#""Ada""#)])]
+    #[case("people[1].age", vec![(Some("/people/1/age"), "12")])]
+    #[case("length(people)", vec![(None, "3")])]
+    #[case("people[0].[name, age]", vec![(None, r#"["Ada", 36]"#)])]
+    #[case("people[?name == 'Zoe']", vec![])]
+    #[case("missing", vec![(None, "null")])]
+    #[case("'literal'", vec![(None, r#""literal""#)])]
+    fn evaluate_paths(#[case] expression: &str, #[case] expected: Vec<(Option<&str>, &str)>) {
+        let root = from_json(
+            r#"{"people": [
+                {"name": "Ada", "age": 36, "tags": ["a"]},
+                {"name": "Alan", "age": 12},
+                {"name": "Grace", "age": 45, "tags": ["b", "c"]}
+            ], "owner": "Ada"}"#,
+        );
+        let runtime = Runtime::get_shared_runtime();
+        let ast = crate::parse(expression).unwrap();
+        let located = Interpreter::new(&runtime, &root)
+            .tracking_paths()
+            .evaluate_paths(&ast)
+            .unwrap();
+        // object wildcards follow key order, so pairs are compared sorted
+        let mut actual: Vec<(Option<String>, String)> = located
+            .into_iter()
+            .map(|x| (x.path.map(|p| p.to_json_pointer()), x.value.to_json()))
+            .collect();
+        let mut expected: Vec<(Option<String>, String)> = expected
+            .into_iter()
+            .map(|(p, v)| (p.map(str::to_string), from_json(v).to_json()))
+            .collect();
+        actual.sort();
+        expected.sort();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn evaluate_paths_untracked() {
+        let root = from_json(r#"{"foo": [1, 2]}"#);
+        let runtime = Runtime::get_shared_runtime();
+        let ast = crate::parse("foo[*]").unwrap();
+        let located = Interpreter::new(&runtime, &root)
+            .evaluate_paths(&ast)
+            .unwrap();
+        assert_eq!(2, located.len());
+        assert!(located.iter().all(Located::is_synthetic));
+    }
 }
@@ -0,0 +1,368 @@
+use crate::Map;
+use crate::Number;
+use crate::Value;
+use crate::functions::DataType;
+use crate::path::{Located, PathSegment, Trail};
+use crate::value_option::ValueOption as _;
+
+/// Provides read access to a JSON-like document model.
+///
+/// The interpreter evaluates JMESPath expressions through this trait,
+/// so that expressions run directly over borrowed documents that use
+/// a representation other than [`Value`]. Only the parts of the document
+/// that end up in the result are converted to a [`Value`].
+///
+/// This trait is implemented for [`Value`] and [`serde_json::Value`].
+/// See [`crate::search_in()`].
+///
+/// # Example
+/// ```
+/// use jmespath_community as jmespath;
+/// use jmespath::JsonLike;
+/// use jmespath::functions::DataType;
+///
+/// let document = serde_json::json!({"foo": [1, 2, 3]});
+/// let foo = document.get("foo").unwrap();
+/// assert_eq!(DataType::Array, JsonLike::data_type(foo));
+/// assert_eq!(3, JsonLike::len(foo));
+/// ```
+pub trait JsonLike {
+    /// Returns the JSON data type of this node.
+    ///
+    /// Only [`DataType::Null`], [`DataType::Boolean`], [`DataType::Number`],
+    /// [`DataType::String`], [`DataType::Array`] and [`DataType::Object`]
+    /// are expected.
+    fn data_type(&self) -> DataType;
+    /// Returns the value of a boolean node.
+    fn as_bool(&self) -> Option<bool>;
+    /// Returns the value of a number node.
+    fn as_number(&self) -> Option<Number>;
+    /// Returns the value of a string node.
+    fn as_str(&self) -> Option<&str>;
+    /// Looks up a member of an object node by its key.
+    fn get(&self, key: &str) -> Option<&Self>;
+    /// Looks up an element of an array node by its zero-based index.
+    fn get_index(&self, index: usize) -> Option<&Self>;
+    /// Returns the number of elements of an array node, or
+    /// the number of members of an object node, and `0` otherwise.
+    fn len(&self) -> usize;
+    /// Returns `true` if this node is an empty array or object,
+    /// or not a container at all.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Iterates over the elements of an array node.
+    ///
+    /// Nodes that are not arrays have no elements.
+    fn elements(&self) -> Box<dyn Iterator<Item = &Self> + '_>;
+    /// Iterates over the members of an object node, in document order.
+    ///
+    /// Nodes that are not objects have no members.
+    fn entries(&self) -> Box<dyn Iterator<Item = (&str, &Self)> + '_>;
+    /// Converts this node and its children to a [`Value`].
+    fn to_value(&self) -> Value {
+        match self.data_type() {
+            DataType::Array => Value::Array(self.elements().map(Self::to_value).collect()),
+            DataType::Boolean => Value::Boolean(self.as_bool().unwrap_or_default()),
+            DataType::Number => self.as_number().map(Value::Number).unwrap_or(Value::Null),
+            DataType::Object => Value::Object(
+                self.entries()
+                    .map(|(key, value)| (key.to_string(), value.to_value()))
+                    .collect::<Map<String, Value>>(),
+            ),
+            DataType::String => Value::String(self.as_str().unwrap_or_default().to_string()),
+            _ => Value::Null,
+        }
+    }
+}
+
+impl JsonLike for Value {
+    fn data_type(&self) -> DataType {
+        self.get_data_type()
+    }
+    fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+    fn as_number(&self) -> Option<Number> {
+        Value::as_number(self).copied()
+    }
+    fn as_str(&self) -> Option<&str> {
+        Value::as_str(self)
+    }
+    fn get(&self, key: &str) -> Option<&Self> {
+        match self {
+            Value::Object(map) => map.get(key),
+            _ => None,
+        }
+    }
+    fn get_index(&self, index: usize) -> Option<&Self> {
+        match self {
+            Value::Array(array) => array.get(index),
+            _ => None,
+        }
+    }
+    fn len(&self) -> usize {
+        match self {
+            Value::Array(array) => array.len(),
+            Value::Object(map) => map.len(),
+            _ => 0,
+        }
+    }
+    fn elements(&self) -> Box<dyn Iterator<Item = &Self> + '_> {
+        match self {
+            Value::Array(array) => Box::new(array.iter()),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+    fn entries(&self) -> Box<dyn Iterator<Item = (&str, &Self)> + '_> {
+        match self {
+            Value::Object(map) => Box::new(map.iter().map(|(k, v)| (k.as_str(), v))),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+    fn to_value(&self) -> Value {
+        self.clone()
+    }
+}
+
+impl JsonLike for serde_json::Value {
+    fn data_type(&self) -> DataType {
+        match self {
+            serde_json::Value::Array(_) => DataType::Array,
+            serde_json::Value::Bool(_) => DataType::Boolean,
+            serde_json::Value::Null => DataType::Null,
+            serde_json::Value::Number(_) => DataType::Number,
+            serde_json::Value::Object(_) => DataType::Object,
+            serde_json::Value::String(_) => DataType::String,
+        }
+    }
+    fn as_bool(&self) -> Option<bool> {
+        serde_json::Value::as_bool(self)
+    }
+    fn as_number(&self) -> Option<Number> {
+        match self {
+            serde_json::Value::Number(_) => Value::map_from_json(self).as_number().copied(),
+            _ => None,
+        }
+    }
+    fn as_str(&self) -> Option<&str> {
+        serde_json::Value::as_str(self)
+    }
+    fn get(&self, key: &str) -> Option<&Self> {
+        self.as_object().and_then(|map| map.get(key))
+    }
+    fn get_index(&self, index: usize) -> Option<&Self> {
+        self.as_array().and_then(|array| array.get(index))
+    }
+    fn len(&self) -> usize {
+        match self {
+            serde_json::Value::Array(array) => array.len(),
+            serde_json::Value::Object(map) => map.len(),
+            _ => 0,
+        }
+    }
+    fn elements(&self) -> Box<dyn Iterator<Item = &Self> + '_> {
+        match self {
+            serde_json::Value::Array(array) => Box::new(array.iter()),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+    fn entries(&self) -> Box<dyn Iterator<Item = (&str, &Self)> + '_> {
+        match self {
+            serde_json::Value::Object(map) => Box::new(map.iter().map(|(k, v)| (k.as_str(), v))),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+    fn to_value(&self) -> Value {
+        Value::map_from_json(self)
+    }
+}
+
+/// A node of the document being searched, as seen by the interpreter.
+///
+/// Nodes of the document are borrowed as long as possible, along with
+/// their location when it is being tracked. Values computed by an expression,
+/// _e.g_ by a function or a multi-select list, are owned.
+/// Projections collect the nodes they select in an array.
+pub(crate) enum Node<'j, J: JsonLike> {
+    Borrowed(&'j J, Trail),
+    Owned(Value),
+    Array(Vec<Node<'j, J>>),
+}
+impl<'j, J: JsonLike> Clone for Node<'j, J> {
+    fn clone(&self) -> Self {
+        match self {
+            Node::Borrowed(node, trail) => Node::Borrowed(node, trail.clone()),
+            Node::Owned(value) => Node::Owned(value.clone()),
+            Node::Array(nodes) => Node::Array(nodes.clone()),
+        }
+    }
+}
+impl<'j, J: JsonLike> Node<'j, J> {
+    pub fn null() -> Self {
+        Node::Owned(Value::Null)
+    }
+    pub fn data_type(&self) -> DataType {
+        match self {
+            Node::Borrowed(node, _) => node.data_type(),
+            Node::Owned(value) => value.get_data_type(),
+            Node::Array(_) => DataType::Array,
+        }
+    }
+    pub fn is_null(&self) -> bool {
+        self.data_type() == DataType::Null
+    }
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Node::Borrowed(node, _) => match node.data_type() {
+                DataType::Array | DataType::Object => !node.is_empty(),
+                DataType::Boolean => node.as_bool().unwrap_or_default(),
+                DataType::Null => false,
+                DataType::String => !node.as_str().unwrap_or_default().is_empty(),
+                _ => true,
+            },
+            Node::Owned(value) => value.is_truthy(),
+            Node::Array(nodes) => !nodes.is_empty(),
+        }
+    }
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Node::Borrowed(node, _) => node.as_str(),
+            Node::Owned(value) => value.as_str(),
+            Node::Array(_) => None,
+        }
+    }
+    /// Returns the member of an object with the specified key, or `null`.
+    pub fn get(&self, key: &str) -> Self {
+        match self {
+            Node::Borrowed(node, trail) => match node.get(key) {
+                Some(member) => {
+                    Node::Borrowed(member, trail.push(|| PathSegment::Key(key.to_string())))
+                }
+                None => Node::null(),
+            },
+            Node::Owned(Value::Object(map)) => Node::Owned(map.get(key).cloned().or_null()),
+            _ => Node::null(),
+        }
+    }
+    /// Returns the element of an array at the specified index, or `null`.
+    ///
+    /// A negative index counts from the end of the array.
+    pub fn at(self, index: isize) -> Self {
+        let len = match &self {
+            Node::Borrowed(node, _) if node.data_type() == DataType::Array => node.len(),
+            Node::Owned(Value::Array(array)) => array.len(),
+            Node::Array(nodes) => nodes.len(),
+            _ => return Node::null(),
+        };
+        let index = if index >= 0 {
+            index
+        } else {
+            index + len as isize
+        };
+        let Ok(index) = usize::try_from(index) else {
+            return Node::null();
+        };
+        match self {
+            Node::Borrowed(node, trail) => match node.get_index(index) {
+                Some(element) => Node::Borrowed(element, trail.push(|| PathSegment::Index(index))),
+                None => Node::null(),
+            },
+            Node::Owned(Value::Array(mut array)) if index < array.len() => {
+                Node::Owned(array.swap_remove(index))
+            }
+            Node::Array(mut nodes) if index < nodes.len() => nodes.swap_remove(index),
+            _ => Node::null(),
+        }
+    }
+    /// Returns the elements of an array.
+    pub fn into_elements(self) -> Option<Vec<Self>> {
+        match self {
+            Node::Borrowed(node, trail) if node.data_type() == DataType::Array => Some(
+                node.elements()
+                    .enumerate()
+                    .map(|(index, element)| {
+                        Node::Borrowed(element, trail.push(|| PathSegment::Index(index)))
+                    })
+                    .collect(),
+            ),
+            Node::Owned(Value::Array(array)) => Some(array.into_iter().map(Node::Owned).collect()),
+            Node::Array(nodes) => Some(nodes),
+            _ => None,
+        }
+    }
+    /// Returns the values of the members of an object.
+    pub fn into_values(self) -> Option<Vec<Self>> {
+        match self {
+            Node::Borrowed(node, trail) if node.data_type() == DataType::Object => Some(
+                node.entries()
+                    .map(|(key, member)| {
+                        Node::Borrowed(member, trail.push(|| PathSegment::Key(key.to_string())))
+                    })
+                    .collect(),
+            ),
+            Node::Owned(Value::Object(map)) => Some(map.into_values().map(Node::Owned).collect()),
+            _ => None,
+        }
+    }
+    /// Converts this node to a [`Value`].
+    pub fn into_value(self) -> Value {
+        match self {
+            Node::Borrowed(node, _) => node.to_value(),
+            Node::Owned(value) => value,
+            Node::Array(nodes) => Value::Array(nodes.into_iter().map(Node::into_value).collect()),
+        }
+    }
+    /// Collects the values selected by this node along with their location.
+    ///
+    /// The elements of an array built by a projection are collected individually.
+    pub fn into_located(self, located: &mut Vec<Located>) {
+        match self {
+            Node::Borrowed(node, trail) => located.push(Located {
+                value: node.to_value(),
+                path: trail.to_path(),
+            }),
+            Node::Owned(value) => located.push(Located { value, path: None }),
+            Node::Array(nodes) => {
+                for node in nodes {
+                    node.into_located(located);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(r#"null"#)]
+    #[case(r#"true"#)]
+    #[case(r#"42"#)]
+    #[case(r#"-1.5"#)]
+    #[case(r#"18446744073709551615"#)]
+    #[case(r#""text""#)]
+    #[case(r#"[1, [2, 3], {"a": null}]"#)]
+    #[case(r#"{"foo": {"bar": [true, false]}, "baz": "qux"}"#)]
+    fn it_converts_serde_json_to_value(#[case] text: &str) {
+        let document: serde_json::Value = serde_json::from_str(text).unwrap();
+        let expected = Value::from_json(text).unwrap();
+        assert_eq!(expected, JsonLike::to_value(&document));
+    }
+
+    #[test]
+    fn it_borrows_document_nodes() {
+        let document = serde_json::json!({"foo": [1, 2, 3]});
+        let node: Node<serde_json::Value> = Node::Borrowed(&document, Trail::root(true));
+        let Node::Borrowed(element, trail) = node.get("foo").at(-1) else {
+            panic!("expected a borrowed node");
+        };
+        assert!(std::ptr::eq(&document["foo"][2], element));
+        assert_eq!("/foo/2", trail.to_path().unwrap().to_json_pointer());
+    }
+}
@@ -3,7 +3,7 @@ use std::rc::Rc;
 use santiago::lexer::Lexeme;
 
 use crate::errors::Error as LexerError;
-use crate::errors::Position;
+use crate::errors::Span;
 
 use crate::errors::error_builder::ErrorBuilder;
 use crate::errors::error_builder::SyntaxErrorBuilder;
@@ -26,7 +26,12 @@ pub(crate) fn tokenize(input: &str) -> Result<Vec<Rc<Lexeme>>, LexerError> {
             };
             let reason = format!("invalid {} near ->{}<-", category, extract);
             Err(LexerError::get_syntax_error_builder()
-                .at(Position::new(error.position.line, error.position.column))
+                .at_span(Span::new(
+                    error.byte_index,
+                    input.len(),
+                    error.position.line,
+                    error.position.column,
+                ))
                 .for_reason(&reason)
                 .build())
         }
@@ -44,10 +49,12 @@ mod tests {
 
     #[rstest]
     // tokens
+    #[case("arrow", "=>")]
     #[case("assign", "=")]
     #[case("colon", ":")]
     #[case("comma", ",")]
     #[case("dot", ".")]
+    #[case("descendant", "..")]
     #[case("pipe", "|")]
     #[case("lparen", "(")]
     #[case("rparen", ")")]
@@ -57,6 +64,7 @@ mod tests {
     #[case("rbracket", "]")]
     #[case("filter", "[?")]
     #[case("flatten", "[]")]
+    #[case("question", "?")]
     #[case("star", "*")]
     #[case("current", "@")]
     #[case("root", "$")]
@@ -120,6 +128,34 @@ mod tests {
     }
 
     #[rstest]
+    #[case("unquoted_string", "# comment\nfoo")]
+    #[case("unquoted_string", "  # comment \n  # another comment\nfoo")]
+    #[case("unquoted_string", "foo # comment")]
+    fn it_skips_comments(#[case] expected: &str, #[case] input: &str) {
+        assert_eq!(expected, get_token(input).kind);
+        assert_eq!(1, get_tokens(input).len());
+    }
+
+    #[rstest]
+    #[case("'# not a comment'", "raw_string")]
+    #[case("\"# not a comment\"", "quoted_string")]
+    #[case("`\"# not a comment\"`", "json_value")]
+    fn it_does_not_skip_comments_in_literals(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(expected, get_token(input).kind);
+        assert_eq!(input, get_token(input).raw);
+    }
+
+    #[test]
+    fn it_tracks_positions_across_comments() {
+        let tokens = get_tokens("foo # comment | bar\n  | baz # comment");
+        let kinds: Vec<_> = tokens.iter().map(|token| token.kind.as_str()).collect();
+        assert_eq!(vec!["unquoted_string", "pipe", "unquoted_string"], kinds);
+        assert_eq!((2, 3), (tokens[1].position.line, tokens[1].position.column));
+        assert_eq!((2, 5), (tokens[2].position.line, tokens[2].position.column));
+    }
+
+    #[rstest]
+    #[case("let_star", "let*")]
     #[case("let", "let")]
     #[case("in", "in")]
     fn it_tokenizes_let_expression_keywords(#[case] expected: &str, #[case] input: &str) {
@@ -127,7 +163,7 @@ mod tests {
     }
 
     #[rstest]
-    #[case("?")]
+    #[case("^")]
     fn it_fails(#[case] input: &str) {
         let result = tokenize(input).map_err(|e| e.kind);
         let expected = Err(Kind::Syntax);
@@ -147,6 +183,8 @@ mod tests {
                 assert!(error.message.contains(contains));
                 assert_eq!(Kind::Syntax, error.kind);
                 assert_eq!(Position::new(pos.0, pos.1), error.position.unwrap());
+                assert_eq!(input.len(), error.span.unwrap().end);
+                assert_eq!(pos.1 - 1, error.span.unwrap().start);
             }
             _ => unreachable!(),
         }
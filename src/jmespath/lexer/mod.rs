@@ -1,4 +1,6 @@
 mod lexer;
 mod rules;
+mod source_map;
 
 pub(crate) use lexer::tokenize;
+pub(crate) use source_map::SourceMap;
@@ -3,10 +3,12 @@ use santiago::lexer::LexerRules;
 pub fn lexer_rules() -> LexerRules {
     santiago::lexer_rules!(
 
+        "DEFAULT" | "arrow" = string "=>";
         "DEFAULT" | "assign" = string "=";
         "DEFAULT" | "colon" = string ":";
         "DEFAULT" | "comma" = string ",";
         "DEFAULT" | "dot" = string ".";
+        "DEFAULT" | "descendant" = string "..";
         "DEFAULT" | "pipe" = string "|";
 
         "DEFAULT" | "lparen" = string "(";
@@ -18,6 +20,7 @@ pub fn lexer_rules() -> LexerRules {
 
         "DEFAULT" | "filter" = string "[?";
         "DEFAULT" | "flatten" = string "[]";
+        "DEFAULT" | "question" = string "?";
 
         "DEFAULT" | "star" = string "*";
         "DEFAULT" | "current" = string "@";
@@ -49,6 +52,7 @@ pub fn lexer_rules() -> LexerRules {
         "DEFAULT" | "number" = pattern r"-?[0-9]+";
 
         // keywords - let expressions
+        "DEFAULT" | "let_star" = string "let*";
         "DEFAULT" | "let" = string "let";
         "DEFAULT" | "in" = string "in";
 
@@ -63,6 +67,9 @@ pub fn lexer_rules() -> LexerRules {
         // bindings
         "DEFAULT" | "variable_ref" = pattern r"\$[A-Za-z_][0-9A-Za-z_]*";
 
+        // Comments "# ..." up to the end of the line will be skipped
+        "DEFAULT" | "COMMENT" = pattern r"#[^\n]*" => |lexer| lexer.skip();
+
         // Whitespace " " will be skipped
         "DEFAULT" | "WS" = pattern r"\s|\u{8}" => |lexer| lexer.skip();
     )
@@ -0,0 +1,78 @@
+use santiago::lexer::Lexeme;
+
+use crate::errors::Span;
+
+/// Maps the line and column of a [`Lexeme`] to byte offsets
+/// within the JMESPath expression it has been extracted from.
+pub(crate) struct SourceMap {
+    input: String,
+    lines: Vec<usize>,
+}
+impl SourceMap {
+    /// Creates a new instance of the [`SourceMap`] type.
+    pub(crate) fn new(input: &str) -> Self {
+        let mut lines = vec![0];
+        lines.extend(input.match_indices('\n').map(|(index, _)| index + 1));
+        SourceMap {
+            input: input.to_string(),
+            lines,
+        }
+    }
+    /// Returns the byte offset of the specified line and column.
+    pub(crate) fn offset(&self, line: usize, column: usize) -> usize {
+        let start = match self.lines.get(line.saturating_sub(1)) {
+            Some(start) => *start,
+            None => return self.input.len(),
+        };
+        self.input[start..]
+            .chars()
+            .take(column.saturating_sub(1))
+            .fold(start, |offset, c| offset + c.len_utf8())
+    }
+    /// Returns an empty [`Span`] located at the end of the expression.
+    pub(crate) fn end(&self) -> Span {
+        let line_start = *self.lines.last().unwrap();
+        let column = self.input[line_start..].chars().count() + 1;
+        let end = self.input.len();
+        Span::new(end, end, self.lines.len(), column)
+    }
+    /// Returns the [`Span`] covered by the specified [`Lexeme`].
+    pub(crate) fn span(&self, lexeme: &Lexeme) -> Span {
+        let (line, column) = (lexeme.position.line, lexeme.position.column);
+        let start = self.offset(line, column);
+        Span::new(start, start + lexeme.raw.len(), line, column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenize;
+    use rstest::*;
+
+    #[rstest]
+    #[case(0, 1, 1)]
+    #[case(4, 1, 5)]
+    #[case(5, 2, 1)]
+    #[case(7, 2, 3)]
+    fn offset(#[case] expected: usize, #[case] line: usize, #[case] column: usize) {
+        let source_map = SourceMap::new("foo.\nbar");
+        assert_eq!(expected, source_map.offset(line, column));
+    }
+
+    #[test]
+    fn end() {
+        let source_map = SourceMap::new("foo.\n'é'");
+        assert_eq!(Span::new(9, 9, 2, 4), source_map.end());
+    }
+
+    #[rstest]
+    #[case("foo.bar", 2, Span::new(4, 7, 1, 5))]
+    #[case("'é' | bar", 2, Span::new(7, 10, 1, 7))]
+    #[case("foo\n  | `true`", 2, Span::new(8, 14, 2, 5))]
+    fn span(#[case] input: &str, #[case] index: usize, #[case] expected: Span) {
+        let source_map = SourceMap::new(input);
+        let tokens = tokenize(input).unwrap();
+        assert_eq!(expected, source_map.span(&tokens[index]));
+    }
+}
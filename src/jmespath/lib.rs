@@ -98,7 +98,9 @@
 //! assert_eq!(None, result);
 //! ```
 mod api;
+mod batch;
 mod lexer;
+mod mutation;
 mod parser;
 mod registry;
 mod scopes;
@@ -111,6 +113,12 @@ pub mod errors;
 pub mod functions;
 /// Contains the main JMESPath expression interpreter.
 pub(crate) mod interpreter;
+/// Abstracts read access to JSON-like document models.
+pub(crate) mod json_like;
+/// Describes the location of values in a JSON document.
+pub(crate) mod path;
+/// Prepared JMESPath expressions with placeholders.
+pub(crate) mod prepared;
 
 pub(crate) mod runtime;
 
@@ -129,10 +137,16 @@ pub use utils::Number;
 pub use utils::map::Map;
 
 pub use errors::Error;
+pub use json_like::JsonLike;
 pub use parser::AST;
+pub use parser::Expr;
 pub use parser::NodeType;
+pub use parser::ParseDiagnostics;
 pub use parser::Slice;
 pub use parser::parse;
+pub use parser::parse_with_diagnostics;
+pub use path::{Located, Path, PathSegment};
+pub use prepared::Prepared;
 pub use runtime::ByFunctionHolder;
 pub use runtime::FunctionContext;
 pub use runtime::FunctionRegistrar;
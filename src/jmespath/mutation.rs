@@ -0,0 +1,229 @@
+use crate::errors::error_builder::{ErrorBuilder, SyntaxErrorBuilder};
+use crate::errors::syntax::SyntaxErrorBuilderFactory;
+use crate::errors::{Error as RuntimeError, Kind};
+use crate::interpreter::Interpreter;
+use crate::parser::AST;
+use crate::{NodeType, Path, Runtime, Value};
+
+/// Updates the values selected by an expression in place.
+///
+/// Returns the number of values that have been updated.
+pub(crate) fn update(
+    runtime: &Runtime,
+    root: &mut Value,
+    ast: &AST,
+    mut f: impl FnMut(&Value) -> Value,
+) -> Result<usize, RuntimeError> {
+    let mut count = 0;
+    for path in locate(runtime, root, ast)? {
+        if let Some(value) = path.resolve_mut(root) {
+            *value = f(value);
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Removes the values selected by an expression from their parent
+/// object or array.
+///
+/// Returns the number of values that have been removed.
+pub(crate) fn delete(
+    runtime: &Runtime,
+    root: &mut Value,
+    ast: &AST,
+) -> Result<usize, RuntimeError> {
+    let paths = locate(runtime, root, ast)?;
+    if paths.iter().any(Path::is_root) {
+        return Err(not_addressable(
+            ast,
+            "the root of the JSON document cannot be deleted",
+        ));
+    }
+    let mut count = 0;
+    for path in paths {
+        if path.remove(root).is_some() {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Resolves the locations selected by an expression.
+///
+/// Locations are sorted deepest and last first, so that
+/// changing one location does not move the remaining ones.
+/// Missing fields or indices select no location.
+fn locate(runtime: &Runtime, root: &Value, ast: &AST) -> Result<Vec<Path>, RuntimeError> {
+    ensure_addressable(ast)?;
+    let mut paths: Vec<Path> = Interpreter::new(runtime, root)
+        .tracking_paths()
+        .evaluate_paths(ast)?
+        .into_iter()
+        .filter_map(|located| located.path)
+        .collect();
+    paths.sort_by(|lhs, rhs| rhs.cmp(lhs));
+    paths.dedup();
+    Ok(paths)
+}
+
+/// Ensures that an expression selects locations in the JSON document
+/// rather than constructing new values.
+///
+/// Fields, indices, slices, filters and wildcard projections select locations.
+/// Filter predicates and index or slice bounds may be arbitrary expressions.
+fn ensure_addressable(ast: &AST) -> Result<(), RuntimeError> {
+    match &ast.node_type {
+        NodeType::None
+        | NodeType::CurrentNode
+        | NodeType::RootNode
+        | NodeType::QuotedIdentifier(_)
+        | NodeType::UnquotedIdentifier(_) => Ok(()),
+        NodeType::DescendantProjection(nodes)
+        | NodeType::HashWildcardProjection(nodes)
+        | NodeType::PipeExpression(nodes)
+        | NodeType::SubExpression(nodes) => nodes.iter().try_for_each(ensure_addressable),
+        // the first node is the kind of projection, e.g. a filter
+        NodeType::Projection(nodes) => nodes[1..].iter().try_for_each(ensure_addressable),
+        NodeType::IndexExpression(nodes) | NodeType::ParenExpression(nodes) => {
+            ensure_addressable(&nodes[0])
+        }
+        NodeType::LetExpression(nodes) => ensure_addressable(&nodes[1]),
+        node_type => {
+            let reason = format!(
+                "{} does not select a location in the JSON document that can be updated or deleted",
+                describe(node_type)
+            );
+            Err(not_addressable(ast, &reason))
+        }
+    }
+}
+fn describe(node_type: &NodeType) -> &'static str {
+    match node_type {
+        NodeType::ArithmeticExpression(_) => "an arithmetic expression",
+        NodeType::ComparatorExpression(_) => "a comparison",
+        NodeType::ConditionalExpression(_) => "a conditional expression",
+        NodeType::Expression(_) | NodeType::LambdaExpression(_) => "an expression reference",
+        NodeType::FunctionExpression(_) => "a function call",
+        NodeType::JsonValue(_) | NodeType::RawString(_) => "a literal",
+        NodeType::LogicalExpression(_) => "a logical expression",
+        NodeType::MultiSelectHash(_) | NodeType::MultiSelectList(_) => "a multi-select",
+        NodeType::Placeholder(_) => "a placeholder",
+        NodeType::VariableRef(_) => "a variable",
+        _ => "the expression",
+    }
+}
+fn not_addressable(ast: &AST, reason: &str) -> RuntimeError {
+    let mut builder = RuntimeError::get_syntax_error_builder();
+    builder
+        .at(ast.position)
+        .set_kind(Kind::InvalidValue)
+        .for_reason(reason);
+    if !ast.span.is_empty() {
+        builder.at_span(ast.span);
+    }
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    const DOCUMENT: &str = r#"{
+        "people": [
+            {"name": "Ada", "age": 36},
+            {"name": "Alan", "age": 12},
+            {"name": "Grace", "age": 45}
+        ],
+        "meta": {"version": 1, "owner": "Ada"}
+    }"#;
+
+    fn from_json(text: &str) -> Value {
+        Value::from_json(text).unwrap()
+    }
+
+    #[rstest]
+    #[case("meta.version", 1, r#"{"version": "updated", "owner": "Ada"}"#)]
+    #[case("meta.*", 2, r#"{"version": "updated", "owner": "updated"}"#)]
+    #[case("meta.missing", 0, r#"{"version": 1, "owner": "Ada"}"#)]
+    #[case(
+        "people[?age > `30`].name | [0]",
+        1,
+        r#"{"version": 1, "owner": "Ada"}"#
+    )]
+    fn update_meta(#[case] expression: &str, #[case] count: usize, #[case] expected: &str) {
+        let runtime = Runtime::get_shared_runtime();
+        let mut root = from_json(DOCUMENT);
+        let ast = crate::parse(expression).unwrap();
+        let updated = update(&runtime, &mut root, &ast, |_| "updated".into()).unwrap();
+        assert_eq!(count, updated);
+        assert_eq!(from_json(expected), root.as_object().unwrap()["meta"]);
+    }
+
+    #[rstest]
+    #[case("people[?age > `30`].age", r#"[37, 12, 46]"#)]
+    #[case("people[-1].age", r#"[36, 12, 46]"#)]
+    #[case("people[::2].age", r#"[37, 12, 46]"#)]
+    #[case("people[*].age", r#"[37, 13, 46]"#)]
+    #[case("let $min = `40` in people[?age > $min].age", r#"[36, 12, 46]"#)]
+    fn update_people(#[case] expression: &str, #[case] expected: &str) {
+        let runtime = Runtime::get_shared_runtime();
+        let mut root = from_json(DOCUMENT);
+        let ast = crate::parse(expression).unwrap();
+        update(&runtime, &mut root, &ast, |v| {
+            (v.as_number().unwrap().as_i64().unwrap() + 1).into()
+        })
+        .unwrap();
+        let ages = crate::search("people[*].age", &root).unwrap();
+        assert_eq!(from_json(expected), ages);
+    }
+
+    #[rstest]
+    #[case("people[?age < `40`]", 2, r#"["Grace"]"#)]
+    #[case("people[0]", 1, r#"["Alan", "Grace"]"#)]
+    #[case("people[:2]", 2, r#"["Grace"]"#)]
+    #[case("people[*].name", 3, r#"[]"#)]
+    #[case("people[5]", 0, r#"["Ada", "Alan", "Grace"]"#)]
+    fn delete_people(#[case] expression: &str, #[case] count: usize, #[case] expected: &str) {
+        let runtime = Runtime::get_shared_runtime();
+        let mut root = from_json(DOCUMENT);
+        let ast = crate::parse(expression).unwrap();
+        assert_eq!(count, delete(&runtime, &mut root, &ast).unwrap());
+        let names = crate::search("people[*].name", &root).unwrap();
+        assert_eq!(from_json(expected), names);
+    }
+
+    #[test]
+    fn delete_nested_locations() {
+        let runtime = Runtime::get_shared_runtime();
+        let mut root = from_json(r#"{"a": {"id": 1, "b": {"id": 2}}, "id": 3}"#);
+        let ast = crate::parse("..id").unwrap();
+        assert_eq!(3, delete(&runtime, &mut root, &ast).unwrap());
+        assert_eq!(from_json(r#"{"a": {"b": {}}}"#), root);
+    }
+
+    #[rstest]
+    #[case("length(people)", "a function call does not select a location", (1, 1))]
+    #[case("people[0].age + `1`", "an arithmetic expression does not select a location", (1, 15))]
+    #[case("people[*].[name, age]", "a multi-select does not select a location", (1, 11))]
+    #[case("people | 'foo'", "a literal does not select a location", (1, 10))]
+    #[case("@", "the root of the JSON document cannot be deleted", (1, 1))]
+    fn delete_rejects_expressions(
+        #[case] expression: &str,
+        #[case] message: &str,
+        #[case] position: (usize, usize),
+    ) {
+        let runtime = Runtime::get_shared_runtime();
+        let mut root = from_json(DOCUMENT);
+        let ast = crate::parse(expression).unwrap();
+        let err = delete(&runtime, &mut root, &ast).unwrap_err();
+        assert_eq!(Kind::InvalidValue, err.kind);
+        assert!(err.message.contains(message), "{}", err.message);
+        assert_eq!(
+            Some(crate::errors::Position::new(position.0, position.1)),
+            err.position
+        );
+        assert_eq!(from_json(DOCUMENT), root);
+    }
+}
@@ -1,5 +1,9 @@
 use super::NodeType;
-use crate::{Map, errors::Position, functions::ReturnValue};
+use crate::{
+    Map,
+    errors::{Position, Span},
+    functions::ReturnValue,
+};
 
 /// Represents an abstract syntax tree node.
 #[derive(Clone)]
@@ -8,15 +12,35 @@ pub struct AST {
     pub node_type: NodeType,
     /// The position of this node in the JMESPath expression.
     pub position: Position,
+    /// The range of the JMESPath expression covered by this node.
+    pub span: Span,
 }
 impl AST {
     /// Creates a new instance of the [`AST`] type.
+    ///
+    /// The [`Span`] of the resulting node covers the spans of its children.
     pub fn make(node_type: NodeType, position: Position) -> Self {
+        let span = node_type
+            .children()
+            .iter()
+            .fold(Span::from(position), |span, child| span.union(&child.span));
         AST {
             node_type,
             position,
+            span,
         }
     }
+    /// Extends the [`Span`] of this node to cover the specified [`Span`].
+    ///
+    /// The line and column of the resulting span match the position of this node.
+    pub(crate) fn spanning(mut self, span: &Span) -> Self {
+        self.span = Span {
+            line: self.position.line,
+            column: self.position.column,
+            ..self.span.union(span)
+        };
+        self
+    }
     /// Evaluates a JMESPath expression.
     ///
     /// # Example
@@ -35,6 +59,92 @@ impl AST {
         let runtime = crate::Runtime::get_shared_runtime();
         runtime.search_ast(self, root)
     }
+    /// Returns the variables that a JMESPath expression references
+    /// without binding them, in order of first appearance.
+    ///
+    /// These must be supplied when searching, _e.g_ using [`crate::search_with()`]
+    /// or [`crate::Runtime::set_global()`]. Variables bound by `let` expressions,
+    /// lambda parameters or builtin functions, such as `$acc` in the expression
+    /// reference argument to `reduce`, are excluded.
+    ///
+    /// # Example
+    /// ```
+    /// use jmespath_community as jmespath;
+    /// use jmespath::parse;
+    ///
+    /// let ast = parse("let $min = $age in people[?age > $min && name != $user]").unwrap();
+    /// assert_eq!(vec!["$age", "$user"], ast.variables());
+    /// ```
+    pub fn variables(&self) -> Vec<String> {
+        let mut variables = Vec::new();
+        collect_variables(self, &mut Vec::new(), &mut variables);
+        variables
+    }
+}
+
+/// Collects the variables referenced by a node that are not bound
+/// by an enclosing `let` expression or lambda expression.
+fn collect_variables(ast: &AST, bound: &mut Vec<String>, free: &mut Vec<String>) {
+    let depth = bound.len();
+    match &ast.node_type {
+        NodeType::VariableRef(name) => {
+            if !bound.contains(name) && !free.contains(name) {
+                free.push(name.clone());
+            }
+        }
+        NodeType::LetExpression(nodes) => {
+            let (bindings, sequential) = match &nodes[0].node_type {
+                NodeType::SequentialLetBindings(bindings) => (bindings, true),
+                _ => (nodes[0].bindings(), false),
+            };
+            // with let each binding sees the enclosing scope only
+            let mut names = Vec::new();
+            for pair in bindings.chunks(2) {
+                collect_variables(&pair[1], bound, free);
+                if sequential {
+                    bind_variables(&pair[0], bound);
+                } else {
+                    bind_variables(&pair[0], &mut names);
+                }
+            }
+            bound.extend(names);
+            collect_variables(&nodes[1], bound, free);
+        }
+        NodeType::FunctionExpression(nodes) => {
+            let names = crate::functions::builtin::bound_variables(nodes[0].function_name());
+            for argument in nodes[1].function_arguments() {
+                if matches!(argument.node_type, NodeType::Expression(..)) {
+                    bound.extend(names.iter().map(|name| name.to_string()));
+                }
+                collect_variables(argument, bound, free);
+                bound.truncate(depth);
+            }
+        }
+        NodeType::LambdaExpression(nodes) => {
+            let (body, parameters) = nodes.split_last().unwrap();
+            for parameter in parameters {
+                bind_variables(parameter, bound);
+            }
+            collect_variables(body, bound, free);
+        }
+        node_type => {
+            for child in node_type.children() {
+                collect_variables(child, bound, free);
+            }
+        }
+    }
+    bound.truncate(depth);
+}
+/// Collects the variables bound by a pattern, _e.g_ `[$a, {name: $b}]`.
+fn bind_variables(pattern: &AST, bound: &mut Vec<String>) {
+    match &pattern.node_type {
+        NodeType::VariableRef(name) => bound.push(name.clone()),
+        node_type => {
+            for child in node_type.children() {
+                bind_variables(child, bound);
+            }
+        }
+    }
 }
 
 impl std::fmt::Debug for AST {
@@ -74,6 +184,7 @@ impl AST {
     ast_!(function_name, UnquotedIdentifier, &String);
     ast_!(raw_string, RawString, &String);
     ast_!(variable_ref, VariableRef, &String);
+    ast_!(placeholder, Placeholder, &String);
     ast_!(bindings, LetBindings, &Vec<AST>);
     ast_!(function_arguments, FunctionArguments, &Vec<AST>);
     pub(crate) fn identifier(&self) -> &String {
@@ -140,6 +251,7 @@ impl AST {
             NodeType::RawString(text) => pretty!(RawString, text),
             NodeType::UnquotedIdentifier(text) => pretty!(UnquotedIdentifier, text),
             NodeType::VariableRef(text) => pretty!(VariableRef, text),
+            NodeType::Placeholder(text) => pretty!(Placeholder, text),
 
             NodeType::ArithmeticExpression(vec) => (
                 "ArithmeticExpression".to_string(),
@@ -149,6 +261,10 @@ impl AST {
                 "ComparatorExpression".to_string(),
                 pretty_print_children(vec, indent_level + 1),
             ),
+            NodeType::ConditionalExpression(vec) => (
+                "ConditionalExpression".to_string(),
+                pretty_print_children(vec, indent_level + 1),
+            ),
 
             NodeType::Expression(vec) => (
                 "Expression".to_string(),
@@ -166,6 +282,10 @@ impl AST {
                 "FunctionExpression".to_string(),
                 pretty_print_children(vec, indent_level + 1),
             ),
+            NodeType::DescendantProjection(vec) => (
+                "DescendantProjection".to_string(),
+                pretty_print_children(vec, indent_level + 1),
+            ),
             NodeType::HashWildcardProjection(vec) => (
                 "HashWildcardProjection".to_string(),
                 pretty_print_children(vec, indent_level + 1),
@@ -174,11 +294,26 @@ impl AST {
                 "Index".to_string(),
                 pretty_print_children(vec, indent_level),
             ),
+            NodeType::ArrayPattern(vec) => (
+                "ArrayPattern".to_string(),
+                pretty_print_children(vec, indent_level + 1),
+            ),
             NodeType::IndexExpression(vec) => (
                 "IndexExpression".to_string(),
                 pretty_print_children(vec, indent_level + 1),
             ),
-            NodeType::LetBindings(vec) => {
+            NodeType::LambdaExpression(vec) => (
+                "LambdaExpression".to_string(),
+                pretty_print_children(vec, indent_level + 1),
+            ),
+            NodeType::LetBindings(vec)
+            | NodeType::SequentialLetBindings(vec)
+            | NodeType::ObjectPattern(vec) => {
+                let name = match &self.node_type {
+                    NodeType::SequentialLetBindings(..) => "SequentialLetBindings",
+                    NodeType::ObjectPattern(..) => "ObjectPattern",
+                    _ => "LetBindings",
+                };
                 let mut children = "".to_string();
                 for index in 0..vec.len() {
                     if index % 2 != 0 {
@@ -190,7 +325,7 @@ impl AST {
                         indent_level + 2,
                     ));
                 }
-                ("LetBindings".to_string(), children)
+                (name.to_string(), children)
             }
             NodeType::LetExpression(vec) => (
                 "LetExpression".to_string(),
@@ -228,6 +363,10 @@ impl AST {
                 format!("Slice[{:?}:{:?}:{:?}]", vec.start, vec.stop, vec.step),
                 "".to_string(),
             ),
+            NodeType::SliceExpression(vec) => (
+                "SliceExpression".to_string(),
+                pretty_print_children(vec, indent_level + 1),
+            ),
             NodeType::SubExpression(vec) => {
                 let children = format!(
                     "\n{}\n{}",
@@ -310,6 +449,7 @@ mod tests {
         NodeType::UnquotedIdentifier("foo".to_string())
     )]
     #[case("VariableRef($foo) [0, 0]", NodeType::VariableRef("$foo".to_string()))]
+    #[case("Placeholder(:foo) [0, 0]", NodeType::Placeholder(":foo".to_string()))]
     fn it_pretty_prints_lexemes_text(#[case] expected: &str, #[case] node_type: NodeType) {
         let ast = AST::make(node_type, Position::default());
         assert_eq!(expected, ast.format());
@@ -374,6 +514,31 @@ mod tests {
         assert_eq!(expected, ast.format());
     }
     #[test]
+    fn it_pretty_prints_conditional_expression() {
+        let nodes = vec![
+            AST::make(
+                NodeType::UnquotedIdentifier("foo".to_string()),
+                Position::new(1, 1),
+            ),
+            AST::make(
+                NodeType::UnquotedIdentifier("bar".to_string()),
+                Position::new(1, 7),
+            ),
+            AST::make(
+                NodeType::UnquotedIdentifier("baz".to_string()),
+                Position::new(1, 13),
+            ),
+        ];
+        let ast = AST::make(NodeType::ConditionalExpression(nodes), Position::new(1, 5));
+        assert_eq!(
+            r#"ConditionalExpression [1, 5]
+  UnquotedIdentifier(foo) [1, 1]
+  UnquotedIdentifier(bar) [1, 7]
+  UnquotedIdentifier(baz) [1, 13]"#,
+            ast.format()
+        );
+    }
+    #[test]
     fn it_pretty_prints_pipe_expression() {
         let nodes = vec![
             AST::make(
@@ -414,4 +579,22 @@ mod tests {
             ast.format()
         );
     }
+
+    #[rstest]
+    #[case("foo", vec![])]
+    #[case("[$user, $user.name, $role]", vec!["$user", "$role"])]
+    #[case("let $a = $b in [$a, $c]", vec!["$b", "$c"])]
+    #[case("let $a = `1`, $b = $a in $b", vec!["$a"])]
+    #[case("let* $a = `1`, $b = $a in $b", vec![])]
+    #[case("let [$a, {name: $b}] = @ in [$a, $b, $c]", vec!["$c"])]
+    #[case("map(($x) => $x + $y, @)", vec!["$y"])]
+    #[case("[let $x = `1` in $x, $x]", vec!["$x"])]
+    #[case("reduce(@, &$acc + @, `0`)", vec![])]
+    #[case("scan(@, &$acc + $step, $acc)", vec!["$step", "$acc"])]
+    #[case("fold(@, ($acc, $x) => $acc + $x)", vec![])]
+    #[case("map(&$acc, @)", vec!["$acc"])]
+    fn it_lists_variables(#[case] expression: &str, #[case] expected: Vec<&str>) {
+        let ast = crate::parse(expression).unwrap();
+        assert_eq!(expected, ast.variables());
+    }
 }
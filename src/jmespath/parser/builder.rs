@@ -0,0 +1,443 @@
+use std::ops;
+
+use super::{AST, NodeType, Slice};
+use crate::errors::Position;
+use crate::path::is_unquoted_identifier;
+use crate::scopes::variable_ref;
+use crate::{Map, Value};
+
+/// Builds a JMESPath [`AST`] programmatically.
+///
+/// Identifiers are quoted and raw strings are escaped as required,
+/// so that the resulting tree never depends on the textual syntax.
+/// Just like in a JMESPath expression, fields, indices, filters and wildcards
+/// that follow a projection apply to each of its elements.
+///
+/// # Example
+/// ```
+/// use jmespath_community as jmespath;
+/// use jmespath::{Expr, Value};
+///
+/// let ast = Expr::field("people")
+///     .filter(Expr::field("age").gt(30))
+///     .project(Expr::field("first name"))
+///     .build();
+///
+/// let data = Value::from_json(r#"{"people": [
+///     {"first name": "Ada", "age": 36},
+///     {"first name": "Alan", "age": 12}
+/// ]}"#).unwrap();
+/// let result = ast.search(&data).unwrap();
+///
+/// assert_eq!(Value::from_json(r#"["Ada"]"#).unwrap(), result);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Expr {
+    ast: AST,
+}
+impl Expr {
+    /// Creates an expression that evaluates to the current node `@`.
+    pub fn current() -> Self {
+        Self::make(NodeType::CurrentNode)
+    }
+    /// Creates an expression that evaluates to the root node `$`.
+    pub fn root() -> Self {
+        Self::make(NodeType::RootNode)
+    }
+    /// Creates an expression that selects a field of the current node.
+    ///
+    /// The name is quoted if it is not a valid unquoted identifier.
+    pub fn field(name: &str) -> Self {
+        Self::make(identifier(name))
+    }
+    /// Creates a reference to a variable, with or without its leading `$`.
+    pub fn variable(name: &str) -> Self {
+        Self::make(NodeType::VariableRef(variable_ref(name)))
+    }
+    /// Creates a placeholder, with or without its leading `:`,
+    /// whose value is bound when searching. See [`crate::Prepared`].
+    pub fn placeholder(name: &str) -> Self {
+        let name = name.strip_prefix(':').unwrap_or(name);
+        Self::make(NodeType::Placeholder(format!(":{}", name)))
+    }
+    /// Creates a JSON literal. _e.g_ `` `42` ``.
+    ///
+    /// # Panics
+    /// Panics if the value holds an expression reference,
+    /// which cannot be represented as JSON.
+    pub fn literal(value: impl Into<Value>) -> Self {
+        Self::make(NodeType::JsonValue(value.into().to_json()))
+    }
+    /// Creates a raw string literal. _e.g_ `'text'`.
+    pub fn string(text: &str) -> Self {
+        Self::make(NodeType::RawString(text.to_string()))
+    }
+    /// Creates a call to the function with the specified name.
+    pub fn function(name: &str, args: impl IntoIterator<Item = Expr>) -> Self {
+        let args = args.into_iter().map(Expr::build).collect();
+        Self::make(NodeType::FunctionExpression(vec![
+            make(NodeType::UnquotedIdentifier(name.to_string())),
+            make(NodeType::FunctionArguments(args)),
+        ]))
+    }
+    /// Creates an expression reference. _e.g_ `&age`.
+    pub fn expref(expression: Expr) -> Self {
+        Self::make(NodeType::Expression(vec![expression.ast]))
+    }
+    /// Creates a multi-select list. _e.g_ `[name, age]`.
+    pub fn list(items: impl IntoIterator<Item = Expr>) -> Self {
+        let items = items.into_iter().map(Expr::build).collect();
+        Self::make(NodeType::MultiSelectList(items))
+    }
+    /// Creates a multi-select hash. _e.g_ `{name: name, years: age}`.
+    ///
+    /// Keys are quoted if they are not valid unquoted identifiers.
+    pub fn hash<'k>(pairs: impl IntoIterator<Item = (&'k str, Expr)>) -> Self {
+        let mut map = Map::new();
+        for (key, expression) in pairs {
+            let key = match identifier(key) {
+                NodeType::UnquotedIdentifier(key) | NodeType::QuotedIdentifier(key) => key,
+                _ => unreachable!(),
+            };
+            map.insert(key, expression.ast);
+        }
+        Self::make(NodeType::MultiSelectHash(map))
+    }
+
+    /// Selects a field. _e.g_ `people.name`.
+    pub fn get(self, name: &str) -> Self {
+        self.postfix(|left| match left {
+            Some(left) => make(NodeType::SubExpression(vec![left, make(identifier(name))])),
+            None => make(identifier(name)),
+        })
+    }
+    /// Selects an element of an array. _e.g_ `people[0]`.
+    ///
+    /// Negative indices count from the end of the array.
+    pub fn index(self, index: i32) -> Self {
+        self.postfix(|left| {
+            make(NodeType::IndexExpression(vec![
+                left.unwrap_or_else(none),
+                make(NodeType::Number(index)),
+            ]))
+        })
+    }
+    /// Projects a slice of an array. _e.g_ `people[1:10:2]`.
+    pub fn slice(self, start: Option<isize>, stop: Option<isize>, step: Option<isize>) -> Self {
+        let slice = NodeType::Slice(Slice { start, stop, step });
+        self.postfix(|left| projection(slice, left))
+    }
+    /// Projects the elements of an array. _e.g_ `people[*]`.
+    pub fn wildcard(self) -> Self {
+        self.postfix(|left| projection(NodeType::ListWildcard, left))
+    }
+    /// Projects the values of an object. _e.g_ `people.*`.
+    pub fn values(self) -> Self {
+        self.postfix(|left| {
+            make(NodeType::HashWildcardProjection(vec![
+                left.unwrap_or_else(none),
+                none(),
+            ]))
+        })
+    }
+    /// Projects the elements of an array that satisfy a predicate.
+    /// _e.g_ `people[?age > `30`]`.
+    pub fn filter(self, predicate: Expr) -> Self {
+        let filter = NodeType::Filter(vec![predicate.ast]);
+        self.postfix(|left| projection(filter, left))
+    }
+    /// Flattens an array of arrays and projects its elements. _e.g_ `people[]`.
+    ///
+    /// Unlike other operations, flattening applies to the result
+    /// of an existing projection rather than to each of its elements.
+    pub fn flatten(self) -> Self {
+        Self {
+            ast: projection(NodeType::Flatten, Some(self.ast)),
+        }
+    }
+    /// Evaluates an expression against each element of a projection.
+    /// _e.g_ `people[*].[name, age]`.
+    ///
+    /// If this expression is not a projection, the elements of
+    /// the resulting array are projected, as if using [`Expr::wildcard()`].
+    pub fn project(self, expression: Expr) -> Self {
+        match self.ast.node_type {
+            NodeType::Projection(..) | NodeType::HashWildcardProjection(..) => {
+                self.postfix(|left| match left {
+                    Some(left) => make(NodeType::SubExpression(vec![left, expression.ast])),
+                    None => expression.ast,
+                })
+            }
+            _ => self.wildcard().project(expression),
+        }
+    }
+    /// Evaluates an expression against the result of this expression,
+    /// stopping any projection. _e.g_ `people[*].name | [0]`.
+    pub fn pipe(self, expression: Expr) -> Self {
+        Self::make(NodeType::PipeExpression(vec![self.ast, expression.ast]))
+    }
+
+    /// Compares for equality. _e.g_ `name == 'Ada'`.
+    pub fn eq(self, rhs: impl Into<Expr>) -> Self {
+        self.compare(NodeType::Equal, rhs.into())
+    }
+    /// Compares for inequality. _e.g_ `name != 'Ada'`.
+    pub fn ne(self, rhs: impl Into<Expr>) -> Self {
+        self.compare(NodeType::NotEqual, rhs.into())
+    }
+    /// Compares using `<`.
+    pub fn lt(self, rhs: impl Into<Expr>) -> Self {
+        self.compare(NodeType::LessThan, rhs.into())
+    }
+    /// Compares using `<=`.
+    pub fn le(self, rhs: impl Into<Expr>) -> Self {
+        self.compare(NodeType::LessThanOrEqual, rhs.into())
+    }
+    /// Compares using `>`.
+    pub fn gt(self, rhs: impl Into<Expr>) -> Self {
+        self.compare(NodeType::GreaterThan, rhs.into())
+    }
+    /// Compares using `>=`.
+    pub fn ge(self, rhs: impl Into<Expr>) -> Self {
+        self.compare(NodeType::GreaterThanOrEqual, rhs.into())
+    }
+    /// Combines with a logical and `&&`.
+    pub fn and(self, rhs: Expr) -> Self {
+        self.binary(NodeType::LogicalExpression, NodeType::And, rhs)
+    }
+    /// Combines with a logical or `||`.
+    pub fn or(self, rhs: Expr) -> Self {
+        self.binary(NodeType::LogicalExpression, NodeType::Or, rhs)
+    }
+
+    /// Returns the abstract syntax tree.
+    pub fn build(self) -> AST {
+        self.ast
+    }
+
+    fn make(node_type: NodeType) -> Self {
+        Self {
+            ast: make(node_type),
+        }
+    }
+    fn compare(self, operator: NodeType, rhs: Expr) -> Self {
+        self.binary(NodeType::ComparatorExpression, operator, rhs)
+    }
+    fn binary(self, node_type: fn(Vec<AST>) -> NodeType, operator: NodeType, rhs: Expr) -> Self {
+        Self::make(node_type(vec![self.ast, make(operator), rhs.ast]))
+    }
+    fn unary(self, node_type: fn(Vec<AST>) -> NodeType, mut nodes: Vec<AST>) -> Self {
+        nodes.push(self.ast);
+        Self::make(node_type(nodes))
+    }
+    /// Applies an operation to this expression or, if this expression
+    /// is a projection, to each of its elements.
+    ///
+    /// The operation receives the expression it applies to,
+    /// or `None` for the elements of a projection.
+    fn postfix(self, operation: impl FnOnce(Option<AST>) -> AST) -> Self {
+        Self {
+            ast: postfix(self.ast, operation),
+        }
+    }
+}
+fn postfix(ast: AST, operation: impl FnOnce(Option<AST>) -> AST) -> AST {
+    let position = ast.position;
+    let (mut nodes, node_type): (_, fn(Vec<AST>) -> NodeType) = match ast.node_type {
+        NodeType::Projection(nodes) => (nodes, NodeType::Projection),
+        NodeType::HashWildcardProjection(nodes) => (nodes, NodeType::HashWildcardProjection),
+        node_type => {
+            return operation(Some(AST {
+                node_type,
+                position,
+                span: ast.span,
+            }));
+        }
+    };
+    // the right-hand side is the last node of the projection
+    let right = nodes.pop().unwrap();
+    let right = match right.node_type {
+        NodeType::None => operation(None),
+        _ => postfix(right, operation),
+    };
+    nodes.push(right);
+    AST::make(node_type(nodes), position)
+}
+fn projection(kind: NodeType, left: Option<AST>) -> AST {
+    make(NodeType::Projection(vec![
+        make(kind),
+        left.unwrap_or_else(none),
+        none(),
+    ]))
+}
+fn identifier(name: &str) -> NodeType {
+    if is_unquoted_identifier(name) {
+        NodeType::UnquotedIdentifier(name.to_string())
+    } else {
+        NodeType::QuotedIdentifier(Value::String(name.to_string()).to_json())
+    }
+}
+fn make(node_type: NodeType) -> AST {
+    AST::make(node_type, Position::default())
+}
+fn none() -> AST {
+    make(NodeType::None)
+}
+
+impl From<Expr> for AST {
+    fn from(expression: Expr) -> Self {
+        expression.ast
+    }
+}
+impl From<AST> for Expr {
+    fn from(ast: AST) -> Self {
+        Self { ast }
+    }
+}
+impl From<Value> for Expr {
+    fn from(value: Value) -> Self {
+        Self::literal(value)
+    }
+}
+impl From<&str> for Expr {
+    fn from(text: &str) -> Self {
+        Self::string(text)
+    }
+}
+impl From<String> for Expr {
+    fn from(text: String) -> Self {
+        Self::string(&text)
+    }
+}
+
+macro_rules! from_ {
+    ($ident:ty) => {
+        impl From<$ident> for Expr {
+            fn from(v: $ident) -> Self {
+                Self::literal(v)
+            }
+        }
+    };
+}
+
+from_! {bool}
+from_! {i32}
+from_! {u32}
+from_! {i64}
+from_! {u64}
+from_! {isize}
+from_! {usize}
+
+impl ops::Not for Expr {
+    type Output = Expr;
+    fn not(self) -> Self::Output {
+        self.unary(NodeType::LogicalExpression, vec![make(NodeType::Not)])
+    }
+}
+impl ops::Neg for Expr {
+    type Output = Expr;
+    fn neg(self) -> Self::Output {
+        self.unary(
+            NodeType::ArithmeticExpression,
+            vec![none(), make(NodeType::Minus)],
+        )
+    }
+}
+
+macro_rules! arithmetic_ {
+    ($trait:ident, $fn:ident, $operator:ident) => {
+        impl<T: Into<Expr>> ops::$trait<T> for Expr {
+            type Output = Expr;
+            fn $fn(self, rhs: T) -> Self::Output {
+                self.binary(
+                    NodeType::ArithmeticExpression,
+                    NodeType::$operator,
+                    rhs.into(),
+                )
+            }
+        }
+    };
+}
+
+arithmetic_! {Add, add, Plus}
+arithmetic_! {Sub, sub, Minus}
+arithmetic_! {Mul, mul, Multiply}
+arithmetic_! {Div, div, Divide}
+arithmetic_! {Rem, rem, Modulo}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    const DOCUMENT: &str = r#"{
+        "people": [
+            {"name": "Ada", "age": 36, "tags": ["math", "code"], "first name": "Augusta"},
+            {"name": "Alan", "age": 12, "tags": ["code"], "first name": "Alan"},
+            {"name": "Grace", "age": 45, "tags": [], "first name": "Grace"}
+        ],
+        "meta": {"owner": "O'Brien", "let": 1, "version": 2}
+    }"#;
+
+    #[rstest]
+    #[case(Expr::field("people").index(-1).get("name"), "people[-1].name")]
+    #[case(Expr::field("people").wildcard().get("name"), "people[*].name")]
+    #[case(Expr::field("people").get("name"), "people.name")]
+    #[case(Expr::field("people").wildcard().get("tags").index(0), "people[*].tags[0]")]
+    #[case(
+        Expr::field("people").filter(Expr::field("age").gt(30)).project(Expr::field("name")),
+        "people[?age > `30`].name"
+    )]
+    #[case(
+        Expr::field("people").project(Expr::field("tags").wildcard()).flatten(),
+        "people[*].tags[*][]"
+    )]
+    #[case(
+        Expr::field("people").wildcard().get("tags").flatten().pipe(Expr::function("length", [Expr::current()])),
+        "people[*].tags[] | length(@)"
+    )]
+    #[case(
+        Expr::field("people").slice(None, None, Some(-1)).project(Expr::list([Expr::field("name"), Expr::field("age") + 1])),
+        "people[::-1].[name, age + `1`]"
+    )]
+    #[case(
+        Expr::field("people").wildcard().project(Expr::hash([("who", Expr::field("first name")), ("first name", -Expr::field("age"))])),
+        r#"people[*].{who: "first name", "first name": -age}"#
+    )]
+    #[case(
+        Expr::field("people").filter(!Expr::field("tags").eq(Value::Array(vec![])).and(Expr::field("age").lt(40))).project(Expr::field("name")),
+        "people[?!(tags == `[]` && age < `40`)].name"
+    )]
+    #[case(Expr::field("meta").values(), "meta.*")]
+    #[case(Expr::field("meta").get("let"), r#"meta."let""#)]
+    #[case(Expr::field("meta").get("owner").eq("O'Brien"), r"meta.owner == 'O\'Brien'")]
+    #[case(
+        Expr::function("min_by", [Expr::field("people"), Expr::expref(Expr::field("age"))]).get("name"),
+        "min_by(people, &age).name"
+    )]
+    #[case(
+        Expr::root().get("people").wildcard().get("age").pipe(Expr::function("length", [Expr::current()]) % 2),
+        "$.people[*].age | length(@) % `2`"
+    )]
+    fn it_builds_expressions(#[case] expression: Expr, #[case] text: &str) {
+        let root = Value::from_json(DOCUMENT).unwrap();
+        let expected = crate::search(text, &root).unwrap();
+        assert_eq!(expected, expression.build().search(&root).unwrap());
+    }
+
+    #[rstest]
+    #[case("name", "UnquotedIdentifier(name) [0, 0]")]
+    #[case("first name", r#"QuotedIdentifier("first name") [0, 0]"#)]
+    #[case("in", r#"QuotedIdentifier("in") [0, 0]"#)]
+    #[case(r#"say "hi""#, r#"QuotedIdentifier("say \"hi\"") [0, 0]"#)]
+    fn it_quotes_identifiers(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(expected, Expr::field(name).build().to_string());
+    }
+
+    #[test]
+    fn it_references_variables() {
+        let expected = "VariableRef($foo) [0, 0]";
+        assert_eq!(expected, Expr::variable("foo").build().to_string());
+        assert_eq!(expected, Expr::variable("$foo").build().to_string());
+    }
+}
@@ -4,9 +4,15 @@ use santiago::{
 };
 
 use super::{AST, NodeType, node_type::Slice};
-use crate::{Map, errors::Position, map};
+use crate::{
+    Map,
+    errors::{Position, Span},
+    lexer::SourceMap,
+    map,
+};
+use std::rc::Rc;
 
-pub fn grammar() -> Grammar<AST> {
+pub fn grammar(source_map: &Rc<SourceMap>) -> Grammar<AST> {
     santiago::grammar!(
 
         // grammar
@@ -17,10 +23,15 @@ pub fn grammar() -> Grammar<AST> {
         "expression" => rules "raw_string";
         "expression" => rules "root";
         "expression" => rules "variable_ref";
+        "expression" => rules "placeholder";
+
+        // placeholder for an expression discarded by error recovery
 
-        "expression" => rules "let" "bindings" "in" "expression" => let_expression;
-        "expression" => rules "lparen" "expression" "rparen" => paren_expression;
-        "expression" => rules "expression" "pipe" "expression" => pipe_expression;
+        "expression" => lexemes "missing" => lexeme(source_map, ignored);
+
+        "expression" => rules "let" "bindings" "in" "expression" => spanned(let_expression);
+        "expression" => rules "lparen" "expression" "rparen" => spanned(paren_expression);
+        "expression" => rules "expression" "pipe" "expression" => spanned(pipe_expression);
 
         // sub-expression
 
@@ -30,196 +41,255 @@ pub fn grammar() -> Grammar<AST> {
         "sub_expression_rhs" => rules "multi_select_hash";
         "sub_expression_rhs" => rules "multi_select_list";
 
-        "expression" => rules "expression" "dot" "sub_expression_rhs" => sub_expression;
+        "expression" => rules "expression" "dot" "sub_expression_rhs" => spanned(sub_expression);
+
+        // descendant projection
+
+        "expression" => rules "descendant" "sub_expression_rhs" => spanned(descendant_projection);
+        "expression" => rules "expression" "descendant" "sub_expression_rhs" => spanned(descendant_projection);
 
         // arithmetic-expression
 
-        "expression" => rules "expression" "div" "expression" => arithmetic_expression;
-        "expression" => rules "expression" "divide" "expression" => arithmetic_expression;
-        "expression" => rules "expression" "minus" "expression" => arithmetic_expression;
-        "expression" => rules "expression" "mod" "expression" => arithmetic_expression;
-        "expression" => rules "expression" "multiply" "expression" => arithmetic_expression;
-        "expression" => rules "expression" "plus" "expression" => arithmetic_expression;
-        "expression" => rules "expression" "star" "expression" => arithmetic_expression;
+        "expression" => rules "expression" "div" "expression" => spanned(arithmetic_expression);
+        "expression" => rules "expression" "divide" "expression" => spanned(arithmetic_expression);
+        "expression" => rules "expression" "minus" "expression" => spanned(arithmetic_expression);
+        "expression" => rules "expression" "mod" "expression" => spanned(arithmetic_expression);
+        "expression" => rules "expression" "multiply" "expression" => spanned(arithmetic_expression);
+        "expression" => rules "expression" "plus" "expression" => spanned(arithmetic_expression);
+        "expression" => rules "expression" "star" "expression" => spanned(arithmetic_expression);
 
-        "unary_arithmetic_expression" => rules "plus" "expression" => arithmetic_expression;
-        "unary_arithmetic_expression" => rules "minus" "expression" => arithmetic_expression;
+        "unary_arithmetic_expression" => rules "plus" "expression" => spanned(arithmetic_expression);
+        "unary_arithmetic_expression" => rules "minus" "expression" => spanned(arithmetic_expression);
 
         "expression" => rules "unary_arithmetic_expression";
 
         // comparator-expression
 
-        "expression" => rules "expression" "equal" "expression" => comparator_expression;
-        "expression" => rules "expression" "greater_than_or_equal" "expression" => comparator_expression;
-        "expression" => rules "expression" "greater_than" "expression" => comparator_expression;
-        "expression" => rules "expression" "less_than_or_equal" "expression" => comparator_expression;
-        "expression" => rules "expression" "less_than" "expression" => comparator_expression;
-        "expression" => rules "expression" "not_equal" "expression" => comparator_expression;
+        "expression" => rules "expression" "equal" "expression" => spanned(comparator_expression);
+        "expression" => rules "expression" "greater_than_or_equal" "expression" => spanned(comparator_expression);
+        "expression" => rules "expression" "greater_than" "expression" => spanned(comparator_expression);
+        "expression" => rules "expression" "less_than_or_equal" "expression" => spanned(comparator_expression);
+        "expression" => rules "expression" "less_than" "expression" => spanned(comparator_expression);
+        "expression" => rules "expression" "not_equal" "expression" => spanned(comparator_expression);
+
+        // conditional-expression
+
+        "expression" => rules "expression" "question" "expression" "colon" "expression" => spanned(conditional_expression);
 
         // filter projection
 
-        "filter_specifier" => rules "filter" "expression" "rbracket" => filter;
+        "filter_specifier" => rules "filter" "expression" "rbracket" => spanned(filter);
 
-        "expression" => rules "filter_specifier" => projection;
-        "expression" => rules "expression" "filter_specifier" => projection;
+        "expression" => rules "filter_specifier" => spanned(projection);
+        "expression" => rules "expression" "filter_specifier" => spanned(projection);
 
         // flatten projection
 
-        "flatten_specifier" => rules "flatten" => flatten;
+        "flatten_specifier" => rules "flatten" => spanned(flatten);
 
-        "expression" => rules "flatten_specifier" => projection;
-        "expression" => rules "expression" "flatten_specifier" => projection;
+        "expression" => rules "flatten_specifier" => spanned(projection);
+        "expression" => rules "expression" "flatten_specifier" => spanned(projection);
 
         // function-expression
 
-        "expression_type" => rules "expref" "expression" => expression_type;
+        "expression_type" => rules "expref" "expression" => spanned(expression_type);
 
-        "function_argument_list" => rules "expression" => function_argument_list;
-        "function_argument_list" => rules "expression_type" => function_argument_list;
-        "function_argument_list" => rules "function_argument_list" "comma" "expression" => function_argument_list;
-        "function_argument_list" => rules "function_argument_list" "comma" "expression_type" => function_argument_list;
+        "function_argument_list" => rules "expression" => spanned(function_argument_list);
+        "function_argument_list" => rules "expression_type" => spanned(function_argument_list);
+        "function_argument_list" => rules "lambda_expression" => spanned(function_argument_list);
+        "function_argument_list" => rules "function_argument_list" "comma" "expression" => spanned(function_argument_list);
+        "function_argument_list" => rules "function_argument_list" "comma" "expression_type" => spanned(function_argument_list);
+        "function_argument_list" => rules "function_argument_list" "comma" "lambda_expression" => spanned(function_argument_list);
 
-        "function_arguments" => rules "lparen" "rparen" => function_arguments;
-        "function_arguments" => rules "lparen" "function_argument_list" "rparen" => function_arguments;
+        "function_arguments" => rules "lparen" "rparen" => spanned(function_arguments);
+        "function_arguments" => rules "lparen" "function_argument_list" "rparen" => spanned(function_arguments);
 
-        "function_expression" => rules "unquoted_string" "function_arguments" => function_expression;
+        "function_expression" => rules "unquoted_string" "function_arguments" => spanned(function_expression);
 
         "expression" => rules "function_expression";
 
         // hash-wildcard projection
 
-        "hash_wildcard_projection" => rules "star" => hash_wildcard;
+        "hash_wildcard_projection" => rules "star" => spanned(hash_wildcard);
 
         "expression" => rules "hash_wildcard_projection";
 
         // index_expression
 
-        "index" => rules "lbracket" "number" "rbracket" => index;
+        "index" => rules "lbracket" "number" "rbracket" => spanned(index);
 
-        "expression" => rules "index" => index_expression;
-        "expression" => rules "expression" "index" => index_expression;
+        // a standalone `[$i]` or `[(expr)]` is a multi-select-list
+        "dynamic_index" => rules "lbracket" "variable_ref" "rbracket" => spanned(index);
+        "dynamic_index" => rules "lbracket" "paren_bound" "rbracket" => spanned(index);
+
+        "expression" => rules "index" => spanned(index_expression);
+        "expression" => rules "expression" "index" => spanned(index_expression);
+        "expression" => rules "expression" "dynamic_index" => spanned(index_expression);
+
+        // index and slice bounds
+
+        "bound" => rules "number";
+        "bound" => rules "variable_ref";
+        "bound" => rules "paren_bound";
+
+        "paren_bound" => rules "lparen" "expression" "rparen" => spanned(paren_expression);
 
         // let-expression
 
-        "bindings" => rules "binding" => let_bindings;
-        "bindings" => rules "bindings" "comma" "binding" => let_bindings;
-        "binding" => rules "variable_ref" "assign" "expression" => let_binding;
+        "bindings" => rules "binding" => spanned(let_bindings);
+        "bindings" => rules "bindings" "comma" "binding" => spanned(let_bindings);
+        "binding" => rules "binding_target" "assign" "expression" => spanned(let_binding);
+
+        // isolates `[` in an array pattern from the precedence of `=`
+        "binding_target" => rules "pattern";
+
+        "expression" => rules "let" "bindings" "in" "expression" => spanned(let_expression);
+        "expression" => rules "let_star" "bindings" "in" "expression" => spanned(sequential_let_expression);
 
-        "expression" => rules "let" "bindings" "in" "expression" => let_expression;
+        // destructuring patterns
+
+        "pattern" => rules "variable_ref";
+        "pattern" => rules "lbracket" "array_patterns" "rbracket" => spanned(array_pattern);
+        "pattern" => rules "lbrace" "object_patterns" "rbrace" => spanned(object_pattern);
+
+        "array_patterns" => rules "pattern" => spanned(array_patterns);
+        "array_patterns" => rules "array_patterns" "comma" "pattern" => spanned(array_patterns);
+
+        "object_patterns" => rules "identifier" "colon" "pattern" => spanned(object_patterns);
+        "object_patterns" => rules "object_patterns" "comma" "identifier" "colon" "pattern" => spanned(object_patterns);
+
+        // lambda-expression
+
+        "lambda_parameters" => rules "variable_ref" => spanned(lambda_parameters);
+        "lambda_parameters" => rules "lambda_parameters" "comma" "variable_ref" => spanned(lambda_parameters);
+
+        "lambda_expression" => rules "lparen" "rparen" "arrow" "expression" => spanned(lambda_expression);
+        "lambda_expression" => rules "lparen" "lambda_parameters" "rparen" "arrow" "expression" => spanned(lambda_expression);
 
         // list-wilcard projection
 
-        "list_wildcard_specifier" => rules "lbracket" "star" "rbracket" => list_wildcard;
+        "list_wildcard_specifier" => rules "lbracket" "star" "rbracket" => spanned(list_wildcard);
 
-        "expression" => rules "list_wildcard_specifier" => projection;
-        "expression" => rules "expression" "list_wildcard_specifier" => projection;
+        "expression" => rules "list_wildcard_specifier" => spanned(projection);
+        "expression" => rules "expression" "list_wildcard_specifier" => spanned(projection);
 
         // logical-expression
 
-        "expression" => rules "expression" "and" "expression" => logical_expression;
-        "expression" => rules "expression" "or" "expression" => logical_expression;
-        "expression" => rules "expression" "not" "expression" => logical_expression;
+        "expression" => rules "expression" "and" "expression" => spanned(logical_expression);
+        "expression" => rules "expression" "or" "expression" => spanned(logical_expression);
+        "expression" => rules "expression" "not" "expression" => spanned(logical_expression);
 
-        "unary_logical_expression" => rules "not" "expression" => logical_expression;
+        "unary_logical_expression" => rules "not" "expression" => spanned(logical_expression);
 
         "expression" => rules "unary_logical_expression";
 
         //multi-select-hash
 
-        "multi_select_hash_key_values" => rules "identifier" "colon" "expression" => multi_select_hash_key_value;
-        "multi_select_hash_key_values" => rules "multi_select_hash_key_values" "comma" "multi_select_hash_key_values" => multi_select_hash_key_values;
+        "multi_select_hash_key_values" => rules "identifier" "colon" "expression" => spanned(multi_select_hash_key_value);
+        "multi_select_hash_key_values" => rules "multi_select_hash_key_values" "comma" "multi_select_hash_key_values" => spanned(multi_select_hash_key_values);
 
-        "multi_select_hash" => rules "lbrace" "multi_select_hash_key_values" "rbrace" => multi_select_hash;
+        "multi_select_hash" => rules "lbrace" "multi_select_hash_key_values" "rbrace" => spanned(multi_select_hash);
 
         "expression" => rules "multi_select_hash";
 
         //multi-select-list
 
-        "multi_select_expressions" => rules "expression" => multi_select_list_expression;
-        "multi_select_expressions" => rules "multi_select_expressions" "comma" "multi_select_expressions" => multi_select_list_expressions;
+        "multi_select_expressions" => rules "expression" => spanned(multi_select_list_expression);
+        "multi_select_expressions" => rules "multi_select_expressions" "comma" "multi_select_expressions" => spanned(multi_select_list_expressions);
 
-        "multi_select_list" => rules "lbracket" "multi_select_expressions" "rbracket" => multi_select_list;
+        "multi_select_list" => rules "lbracket" "multi_select_expressions" "rbracket" => spanned(multi_select_list);
 
         "expression" => rules "multi_select_list";
 
         // paren-expression
 
-        "expression" => rules "lparen" "expression" "rparen" => paren_expression;
+        "expression" => rules "lparen" "expression" "rparen" => spanned(paren_expression);
 
         // pipe-expression
 
-        "expression" => rules "expression" "pipe" "expression" => pipe_expression;
+        "expression" => rules "expression" "pipe" "expression" => spanned(pipe_expression);
+
+        // placeholder
+
+        "placeholder" => rules "colon" "unquoted_string" => spanned(placeholder);
 
         // slice projection
 
-        "slice" => rules "lbracket"          "colon"                           "rbracket" => |nodes| slice_bracket(None, None, None, nodes[0].position);
-        "slice" => rules "lbracket" "number" "colon"                           "rbracket" => |nodes| slice_bracket(Some(nodes[1].number()), None, None, nodes[0].position);
-        "slice" => rules "lbracket"          "colon" "number"                  "rbracket" => |nodes| slice_bracket(None, Some(nodes[2].number()), None, nodes[0].position);
-        "slice" => rules "lbracket" "number" "colon" "number"                  "rbracket" => |nodes| slice_bracket(Some(nodes[1].number()), Some(nodes[3].number()), None, nodes[0].position);
+        "slice" => rules "lbracket"         "colon"                         "rbracket" => spanned(|nodes| slice_bracket(None, None, None, nodes[0].position));
+        "slice" => rules "lbracket" "bound" "colon"                         "rbracket" => spanned(|nodes| slice_bracket(Some(nodes[1].clone()), None, None, nodes[0].position));
+        "slice" => rules "lbracket"         "colon" "bound"                 "rbracket" => spanned(|nodes| slice_bracket(None, Some(nodes[2].clone()), None, nodes[0].position));
+        "slice" => rules "lbracket" "bound" "colon" "bound"                 "rbracket" => spanned(|nodes| slice_bracket(Some(nodes[1].clone()), Some(nodes[3].clone()), None, nodes[0].position));
 
-        "slice" => rules "lbracket"          "colon"          "colon"          "rbracket" => |nodes| slice_bracket(None, None, None, nodes[0].position);
-        "slice" => rules "lbracket" "number" "colon"          "colon"          "rbracket" => |nodes| slice_bracket(Some(nodes[1].number()), None, None, nodes[0].position);
-        "slice" => rules "lbracket"          "colon" "number" "colon"          "rbracket" => |nodes| slice_bracket(None, Some(nodes[2].number()), None, nodes[0].position);
-        "slice" => rules "lbracket" "number" "colon" "number" "colon"          "rbracket" => |nodes| slice_bracket(Some(nodes[1].number()), Some(nodes[3].number()), None, nodes[0].position);
+        "slice" => rules "lbracket"         "colon"         "colon"         "rbracket" => spanned(|nodes| slice_bracket(None, None, None, nodes[0].position));
+        "slice" => rules "lbracket" "bound" "colon"         "colon"         "rbracket" => spanned(|nodes| slice_bracket(Some(nodes[1].clone()), None, None, nodes[0].position));
+        "slice" => rules "lbracket"         "colon" "bound" "colon"         "rbracket" => spanned(|nodes| slice_bracket(None, Some(nodes[2].clone()), None, nodes[0].position));
+        "slice" => rules "lbracket" "bound" "colon" "bound" "colon"         "rbracket" => spanned(|nodes| slice_bracket(Some(nodes[1].clone()), Some(nodes[3].clone()), None, nodes[0].position));
 
-        "slice" => rules "lbracket"          "colon"          "colon" "number" "rbracket" => |nodes| slice_bracket(None, None, Some(nodes[3].number()), nodes[0].position);
-        "slice" => rules "lbracket" "number" "colon"          "colon" "number" "rbracket" => |nodes| slice_bracket(Some(nodes[1].number()), None, Some(nodes[4].number()), nodes[0].position);
-        "slice" => rules "lbracket"          "colon" "number" "colon" "number" "rbracket" => |nodes| slice_bracket(None, Some(nodes[2].number()), Some(nodes[4].number()), nodes[0].position);
-        "slice" => rules "lbracket" "number" "colon" "number" "colon" "number" "rbracket" => |nodes| slice_bracket(Some(nodes[1].number()), Some(nodes[3].number()), Some(nodes[5].number()), nodes[0].position);
+        "slice" => rules "lbracket"         "colon"         "colon" "bound" "rbracket" => spanned(|nodes| slice_bracket(None, None, Some(nodes[3].clone()), nodes[0].position));
+        "slice" => rules "lbracket" "bound" "colon"         "colon" "bound" "rbracket" => spanned(|nodes| slice_bracket(Some(nodes[1].clone()), None, Some(nodes[4].clone()), nodes[0].position));
+        "slice" => rules "lbracket"         "colon" "bound" "colon" "bound" "rbracket" => spanned(|nodes| slice_bracket(None, Some(nodes[2].clone()), Some(nodes[4].clone()), nodes[0].position));
+        "slice" => rules "lbracket" "bound" "colon" "bound" "colon" "bound" "rbracket" => spanned(|nodes| slice_bracket(Some(nodes[1].clone()), Some(nodes[3].clone()), Some(nodes[5].clone()), nodes[0].position));
 
-        "expression" => rules "slice" => projection;
-        "expression" => rules "expression" "slice" => projection;
+        "expression" => rules "slice" => spanned(projection);
+        "expression" => rules "expression" "slice" => spanned(projection);
 
         // other lexemes
 
-        "identifier" => lexemes "quoted_string"        => quoted_string;
-        "identifier" => lexemes "unquoted_string"      => unquoted_string;
-
-        "current" => lexemes "current"                 => current_node;
-        "json_value" => lexemes "json_value"           => json_value;
-        "number" => lexemes "number"                   => number;
-        "quoted_string" => lexemes "quoted_string"     => quoted_string;
-        "raw_string" => lexemes "raw_string"           => raw_string;
-        "root" => lexemes "root"                       => root_node;
-        "unquoted_string" => lexemes "unquoted_string" => unquoted_string;
-        "variable_ref" => lexemes "variable_ref"       => variable_ref;
-
-        "div" => lexemes "div"           => arithmetic_div;
-        "divide" => lexemes "divide"     => arithmetic_divide;
-        "minus" => lexemes "minus"       => arithmetic_minus;
-        "mod" => lexemes "mod"           => arithmetic_modulo;
-        "multiply" => lexemes "multiply" => arithmetic_multiply;
-        "plus" => lexemes "plus"         => arithmetic_plus;
-        "star" => lexemes "star"         => arithmetic_multiply;
-
-        "and" => lexemes "and"           => logical_and;
-        "or" => lexemes "or"             => logical_or;
-        "not" => lexemes "not"           => logical_not;
-
-        "equal" => lexemes "equal"                                 => comparator_equal;
-        "greater_than_or_equal" => lexemes "greater_than_or_equal" => comparator_greater_than_or_equal;
-        "greater_than" => lexemes "greater_than"                   => comparator_greater_than;
-        "less_than_or_equal" => lexemes "less_than_or_equal"       => comparator_less_than_or_equal;
-        "less_than" => lexemes "less_than"                         => comparator_less_than;
-        "not_equal" => lexemes "not_equal"                         => comparator_not_equal;
-
-        "assign" => lexemes "assign"     => ignored;
-        "colon" => lexemes "colon"       => ignored;
-        "comma" => lexemes "comma"       => ignored;
-        "dot" => lexemes "dot"           => ignored;
-        "expref" => lexemes "expref"     => ignored;
-        "filter" => lexemes "filter"     => ignored;
-        "flatten" => lexemes "flatten"   => ignored;
-        "in" => lexemes "in"             => ignored;
-        "lbrace" => lexemes "lbrace"     => ignored;
-        "lbracket" => lexemes "lbracket" => ignored;
-        "let" => lexemes "let"           => ignored;
-        "list" => lexemes "list"         => ignored;
-        "lparen" => lexemes "lparen"     => ignored;
-        "pipe" => lexemes "pipe"         => ignored;
-        "rbrace" => lexemes "rbrace"     => ignored;
-        "rbracket" => lexemes "rbracket" => ignored;
-        "rparen" => lexemes "rparen"     => ignored;
+        "identifier" => lexemes "quoted_string"        => lexeme(source_map, quoted_string);
+        "identifier" => lexemes "unquoted_string"      => lexeme(source_map, unquoted_string);
+
+        "current" => lexemes "current"                 => lexeme(source_map, current_node);
+        "json_value" => lexemes "json_value"           => lexeme(source_map, json_value);
+        "number" => lexemes "number"                   => lexeme(source_map, number);
+        "quoted_string" => lexemes "quoted_string"     => lexeme(source_map, quoted_string);
+        "raw_string" => lexemes "raw_string"           => lexeme(source_map, raw_string);
+        "root" => lexemes "root"                       => lexeme(source_map, root_node);
+        "unquoted_string" => lexemes "unquoted_string" => lexeme(source_map, unquoted_string);
+        "variable_ref" => lexemes "variable_ref"       => lexeme(source_map, variable_ref);
+
+        "div" => lexemes "div"           => lexeme(source_map, arithmetic_div);
+        "divide" => lexemes "divide"     => lexeme(source_map, arithmetic_divide);
+        "minus" => lexemes "minus"       => lexeme(source_map, arithmetic_minus);
+        "mod" => lexemes "mod"           => lexeme(source_map, arithmetic_modulo);
+        "multiply" => lexemes "multiply" => lexeme(source_map, arithmetic_multiply);
+        "plus" => lexemes "plus"         => lexeme(source_map, arithmetic_plus);
+        "star" => lexemes "star"         => lexeme(source_map, arithmetic_multiply);
+
+        "and" => lexemes "and"           => lexeme(source_map, logical_and);
+        "or" => lexemes "or"             => lexeme(source_map, logical_or);
+        "not" => lexemes "not"           => lexeme(source_map, logical_not);
+
+        "equal" => lexemes "equal"                                 => lexeme(source_map, comparator_equal);
+        "greater_than_or_equal" => lexemes "greater_than_or_equal" => lexeme(source_map, comparator_greater_than_or_equal);
+        "greater_than" => lexemes "greater_than"                   => lexeme(source_map, comparator_greater_than);
+        "less_than_or_equal" => lexemes "less_than_or_equal"       => lexeme(source_map, comparator_less_than_or_equal);
+        "less_than" => lexemes "less_than"                         => lexeme(source_map, comparator_less_than);
+        "not_equal" => lexemes "not_equal"                         => lexeme(source_map, comparator_not_equal);
+
+        "arrow" => lexemes "arrow"       => lexeme(source_map, ignored);
+        "assign" => lexemes "assign"     => lexeme(source_map, ignored);
+        "colon" => lexemes "colon"       => lexeme(source_map, ignored);
+        "comma" => lexemes "comma"       => lexeme(source_map, ignored);
+        "descendant" => lexemes "descendant" => lexeme(source_map, ignored);
+        "dot" => lexemes "dot"           => lexeme(source_map, ignored);
+        "expref" => lexemes "expref"     => lexeme(source_map, ignored);
+        "filter" => lexemes "filter"     => lexeme(source_map, ignored);
+        "flatten" => lexemes "flatten"   => lexeme(source_map, ignored);
+        "in" => lexemes "in"             => lexeme(source_map, ignored);
+        "lbrace" => lexemes "lbrace"     => lexeme(source_map, ignored);
+        "lbracket" => lexemes "lbracket" => lexeme(source_map, ignored);
+        "let" => lexemes "let"           => lexeme(source_map, ignored);
+        "let_star" => lexemes "let_star" => lexeme(source_map, ignored);
+        "list" => lexemes "list"         => lexeme(source_map, ignored);
+        "lparen" => lexemes "lparen"     => lexeme(source_map, ignored);
+        "pipe" => lexemes "pipe"         => lexeme(source_map, ignored);
+        "question" => lexemes "question" => lexeme(source_map, ignored);
+        "rbrace" => lexemes "rbrace"     => lexeme(source_map, ignored);
+        "rbracket" => lexemes "rbracket" => lexeme(source_map, ignored);
+        "rparen" => lexemes "rparen"     => lexeme(source_map, ignored);
+
+        // the body of a lambda-expression extends as far as possible
+        Associativity::Right => rules "arrow";
 
         Associativity::Left => rules "lbracket";
         Associativity::Left => rules "star";
@@ -231,6 +301,8 @@ pub fn grammar() -> Grammar<AST> {
 
         Associativity::Left => rules "pipe";
 
+        Associativity::Right => rules "question";
+
         Associativity::Left => rules "not";
         Associativity::Left => rules "or";
         Associativity::Left => rules "and";
@@ -249,10 +321,35 @@ pub fn grammar() -> Grammar<AST> {
         Associativity::Left => rules "mod";
         Associativity::Left => rules "div";
 
-        Associativity::Left => rules "dot";
+        Associativity::Left => rules "dot" "descendant";
     )
 }
 
+/// Computes the [`Span`] of the [`AST`] built by a lexeme production action.
+fn lexeme<F>(source_map: &Rc<SourceMap>, action: F) -> impl Fn(&[&Rc<Lexeme>]) -> AST + 'static
+where
+    F: Fn(&[&Rc<Lexeme>]) -> AST + 'static,
+{
+    let source_map = source_map.clone();
+    move |lexemes| {
+        let span = lexemes.iter().fold(Span::default(), |span, lexeme| {
+            span.union(&source_map.span(lexeme))
+        });
+        action(lexemes).spanning(&span)
+    }
+}
+
+/// Extends the [`Span`] of the [`AST`] built by a rule production action
+/// to cover all the symbols of the production, including ignored tokens.
+fn spanned(action: fn(Vec<AST>) -> AST) -> impl Fn(Vec<AST>) -> AST + 'static {
+    move |nodes| {
+        let span = nodes
+            .iter()
+            .fold(Span::default(), |span, node| span.union(&node.span));
+        action(nodes).spanning(&span)
+    }
+}
+
 macro_rules! pos(
     ($ident:ident) => {
         Position::new($ident[0].position.line, $ident[0].position.column)
@@ -347,6 +444,10 @@ fn variable_ref<'a, 'b>(lexemes: &'a [&'b std::rc::Rc<Lexeme>]) -> AST {
     let text = &lexemes[0].raw;
     AST::make(NodeType::VariableRef(text.to_string()), pos!(lexemes))
 }
+fn placeholder(nodes: Vec<AST>) -> AST {
+    let name = format!(":{}", nodes[1].identifier());
+    AST::make(NodeType::Placeholder(name), nodes[0].position)
+}
 
 fn arithmetic_expression(nodes: Vec<AST>) -> AST {
     if nodes.len() == 2 {
@@ -364,12 +465,61 @@ fn arithmetic_expression(nodes: Vec<AST>) -> AST {
         nodes[1].position,
     )
 }
+fn array_pattern(nodes: Vec<AST>) -> AST {
+    let mut pattern = nodes[1].clone();
+    pattern.position = nodes[0].position;
+    pattern
+}
+fn array_patterns(nodes: Vec<AST>) -> AST {
+    // "pattern"
+    // "array_patterns" "comma" "pattern"
+    let mut children = match nodes.len() {
+        1 => vec![],
+        _ => match &nodes[0].node_type {
+            NodeType::ArrayPattern(patterns) => patterns.clone(),
+            _ => unreachable!(),
+        },
+    };
+    children.push(nodes[nodes.len() - 1].clone());
+    AST::make(NodeType::ArrayPattern(children), nodes[0].position)
+}
 fn comparator_expression(nodes: Vec<AST>) -> AST {
     AST::make(
         NodeType::ComparatorExpression(vec![nodes[0].clone(), nodes[1].clone(), nodes[2].clone()]),
         nodes[1].position,
     )
 }
+fn conditional_expression(nodes: Vec<AST>) -> AST {
+    AST::make(
+        NodeType::ConditionalExpression(vec![nodes[0].clone(), nodes[2].clone(), nodes[4].clone()]),
+        nodes[1].position,
+    )
+}
+fn descendant_projection(nodes: Vec<AST>) -> AST {
+    // "descendant" "sub_expression_rhs"
+    // "expression" "descendant" "sub_expression_rhs"
+    let (left, descendant, right) = match nodes.len() {
+        2 => (
+            AST::make(NodeType::None, Position::new(0, 0)),
+            &nodes[0],
+            &nodes[1],
+        ),
+        _ => (nodes[0].clone(), &nodes[1], &nodes[2]),
+    };
+    if let NodeType::LetExpression(children) = &left.node_type {
+        // let-expression needs refactoring (see fn projection)
+        let expression =
+            descendant_projection(vec![children[1].clone(), descendant.clone(), right.clone()]);
+        return AST::make(
+            NodeType::LetExpression(vec![children[0].clone(), expression]),
+            left.position,
+        );
+    }
+    AST::make(
+        NodeType::DescendantProjection(vec![left, right.clone()]),
+        descendant.position,
+    )
+}
 fn expression_type(nodes: Vec<AST>) -> AST {
     AST::make(
         NodeType::Expression(vec![nodes[1].clone()]),
@@ -494,6 +644,13 @@ fn index_expression(nodes: Vec<AST>) -> AST {
                 &index_expression(vec![children[1].clone(), nodes[1].clone()]),
             );
         }
+        NodeType::DescendantProjection(children) => {
+            let expression = index_expression(vec![children[1].clone(), nodes[1].clone()]);
+            return AST::make(
+                NodeType::DescendantProjection(vec![children[0].clone(), expression]),
+                nodes[0].position,
+            );
+        }
         _ => {}
     }
     match &nodes[0].node_type {
@@ -509,6 +666,32 @@ fn index_expression(nodes: Vec<AST>) -> AST {
     }
     AST::make(NodeType::IndexExpression(children), position)
 }
+fn lambda_parameters(nodes: Vec<AST>) -> AST {
+    match nodes.len() {
+        1 => AST::make(
+            NodeType::LambdaExpression(vec![nodes[0].clone()]),
+            nodes[0].position,
+        ),
+        3 => match &nodes[0].node_type {
+            NodeType::LambdaExpression(parameters) => {
+                let mut parameters = parameters.clone();
+                parameters.push(nodes[2].clone());
+                AST::make(NodeType::LambdaExpression(parameters), nodes[0].position)
+            }
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+}
+fn lambda_expression(nodes: Vec<AST>) -> AST {
+    // parameters are collected into a lambda-expression without a body
+    let mut children = match &nodes[1].node_type {
+        NodeType::LambdaExpression(parameters) => parameters.clone(),
+        _ => vec![],
+    };
+    children.push(nodes[nodes.len() - 1].clone());
+    AST::make(NodeType::LambdaExpression(children), nodes[0].position)
+}
 fn let_binding(nodes: Vec<AST>) -> AST {
     AST::make(
         NodeType::LetBindings(vec![nodes[0].clone(), nodes[2].clone()]),
@@ -535,6 +718,16 @@ fn let_expression(nodes: Vec<AST>) -> AST {
         nodes[0].position,
     )
 }
+fn sequential_let_expression(nodes: Vec<AST>) -> AST {
+    let bindings = AST::make(
+        NodeType::SequentialLetBindings(nodes[1].bindings().clone()),
+        nodes[1].position,
+    );
+    AST::make(
+        NodeType::LetExpression(vec![bindings, nodes[3].clone()]),
+        nodes[0].position,
+    )
+}
 fn list_wildcard(nodes: Vec<AST>) -> AST {
     AST::make(NodeType::ListWildcard, nodes[0].position)
 }
@@ -600,6 +793,25 @@ fn multi_select_list_expressions(nodes: Vec<AST>) -> AST {
     }
     AST::make(NodeType::MultiSelectList(args), nodes[0].position)
 }
+fn object_pattern(nodes: Vec<AST>) -> AST {
+    let mut pattern = nodes[1].clone();
+    pattern.position = nodes[0].position;
+    pattern
+}
+fn object_patterns(nodes: Vec<AST>) -> AST {
+    // "identifier" "colon" "pattern"
+    // "object_patterns" "comma" "identifier" "colon" "pattern"
+    let (mut children, key, pattern) = match nodes.len() {
+        3 => (vec![], &nodes[0], &nodes[2]),
+        _ => match &nodes[0].node_type {
+            NodeType::ObjectPattern(patterns) => (patterns.clone(), &nodes[2], &nodes[4]),
+            _ => unreachable!(),
+        },
+    };
+    children.push(key.clone());
+    children.push(pattern.clone());
+    AST::make(NodeType::ObjectPattern(children), nodes[0].position)
+}
 fn paren_expression(nodes: Vec<AST>) -> AST {
     AST::make(
         NodeType::ParenExpression(vec![nodes[1].clone()]),
@@ -652,13 +864,15 @@ fn make_projection(projection_type: &AST, left: &AST, right: &AST) -> AST {
 }
 fn projection(nodes: Vec<AST>) -> AST {
     match &nodes[0].node_type {
-        NodeType::Filter(..) | NodeType::Flatten | NodeType::ListWildcard | NodeType::Slice(..) => {
-            make_projection(
-                &nodes[0],
-                &AST::make(NodeType::None, Position::new(0, 0)),
-                &AST::make(NodeType::None, Position::new(0, 0)),
-            )
-        }
+        NodeType::Filter(..)
+        | NodeType::Flatten
+        | NodeType::ListWildcard
+        | NodeType::Slice(..)
+        | NodeType::SliceExpression(..) => make_projection(
+            &nodes[0],
+            &AST::make(NodeType::None, Position::new(0, 0)),
+            &AST::make(NodeType::None, Position::new(0, 0)),
+        ),
         NodeType::LetExpression(children) => {
             // I have not been able to successfully configure the parser for let-expression.
             // For instance the following expression yields two different parse trees:
@@ -678,6 +892,14 @@ fn projection(nodes: Vec<AST>) -> AST {
                 nodes[0].position,
             )
         }
+        NodeType::DescendantProjection(children) => {
+            // projects the right-hand side of each descendant
+            let expression = projection(vec![children[1].clone(), nodes[1].clone()]);
+            AST::make(
+                NodeType::DescendantProjection(vec![children[0].clone(), expression]),
+                nodes[0].position,
+            )
+        }
         NodeType::Projection(..) => make_projection(&nodes[1], &nodes[0], &nodes[1]),
         _ => make_projection(
             &nodes[1],
@@ -687,19 +909,26 @@ fn projection(nodes: Vec<AST>) -> AST {
     }
 }
 fn slice_bracket(
-    start: Option<i32>,
-    stop: Option<i32>,
-    step: Option<i32>,
+    start: Option<AST>,
+    stop: Option<AST>,
+    step: Option<AST>,
     position: Position,
 ) -> AST {
-    AST::make(
-        NodeType::Slice(Slice {
-            start: start.map(|x| x as isize),
-            stop: stop.map(|x| x as isize),
-            step: step.map(|x| x as isize),
-        }),
-        position,
-    )
+    let bounds = [start, stop, step];
+    let is_static = bounds
+        .iter()
+        .flatten()
+        .all(|bound| matches!(bound.node_type, NodeType::Number(..)));
+    if is_static {
+        let [start, stop, step] = bounds.map(|bound| bound.map(|x| x.number() as isize));
+        return AST::make(NodeType::Slice(Slice { start, stop, step }), position);
+    }
+    // bounds with variables or expressions are evaluated at runtime
+    let bounds = bounds
+        .into_iter()
+        .map(|bound| bound.unwrap_or_else(|| AST::make(NodeType::None, Position::new(0, 0))))
+        .collect();
+    AST::make(NodeType::SliceExpression(bounds), position)
 }
 fn make_sub_expression(nodes: Vec<AST>) -> AST {
     match &nodes[0].node_type {
@@ -767,6 +996,18 @@ fn sub_expression(nodes: Vec<AST>) -> AST {
         (NodeType::HashWildcardProjection(..), NodeType::HashWildcardProjection(..)) => {
             make_sub_expression(nodes)
         }
+        (NodeType::DescendantProjection(children), _) => {
+            // projects the right-hand side of each descendant
+            let expression = sub_expression(vec![
+                children[1].clone(),
+                nodes[1].clone(),
+                nodes[2].clone(),
+            ]);
+            AST::make(
+                NodeType::DescendantProjection(vec![children[0].clone(), expression]),
+                nodes[0].position,
+            )
+        }
         (_, NodeType::HashWildcardProjection(list)) => {
             let mut vec = list.clone();
             vec[0] = nodes[0].clone();
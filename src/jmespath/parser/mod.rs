@@ -1,9 +1,15 @@
 mod ast;
-mod grammar;
+mod builder;
+pub(crate) mod grammar;
 mod node_type;
 mod parser;
+mod recovery;
 
 pub use ast::AST;
+pub use builder::Expr;
 pub use node_type::NodeType;
 pub use node_type::Slice;
+pub use parser::ParseDiagnostics;
 pub use parser::parse;
+pub use parser::parse_with_diagnostics;
+pub(crate) use recovery::MISSING;
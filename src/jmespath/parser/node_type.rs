@@ -68,6 +68,16 @@ pub enum NodeType {
     /// assert_eq!("(1, 1):VariableRef(\"$foo\")", format!("{:?}", ast));
     /// assert!(matches!(ast.node_type, NodeType::VariableRef(..)));
     VariableRef(String),
+    /// A placeholder for a value bound when evaluating a [`crate::Prepared`] expression.
+    /// _e.g_ `:foo`.
+    /// # Example
+    /// ```
+    /// use jmespath_community as jmespath;
+    /// use jmespath::{AST, NodeType};
+    /// let ast = jmespath::parse(":foo").unwrap();
+    /// assert_eq!("(1, 1):Placeholder(\":foo\")", format!("{:?}", ast));
+    /// assert!(matches!(ast.node_type, NodeType::Placeholder(..)));
+    Placeholder(String),
 
     /// A reference `$` to the root (input) value.
     /// # Example
@@ -127,6 +137,18 @@ pub enum NodeType {
     /// let ast = jmespath::parse("[::-1]").unwrap();
     /// assert_eq!("(1, 1):Projection([(1, 1):Slice(Slice { start: None, stop: None, step: Some(-1) }), (0, 0):None, (0, 0):None])", format!("{:?}", ast));
     Slice(Slice),
+    /// The slice `[::]` [`NodeType::Projection`] whose bounds are evaluated at runtime.
+    ///
+    /// A slice expression has three nodes, for the start, stop and step bounds.
+    /// Each bound is either [`NodeType::None`], a [`NodeType::Number`],
+    /// a [`NodeType::VariableRef`] or a [`NodeType::ParenExpression`].
+    /// # Example
+    /// ```
+    /// use jmespath_community as jmespath;
+    /// use jmespath::{AST, NodeType};
+    /// let ast = jmespath::parse("[$i:]").unwrap();
+    /// assert_eq!("(1, 1):Projection([(1, 1):SliceExpression([(1, 2):VariableRef(\"$i\"), (0, 0):None, (0, 0):None]), (0, 0):None, (0, 0):None])", format!("{:?}", ast));
+    SliceExpression(Vec<AST>),
 
     /// The plus `+` arithmetic operator.
     /// # Example
@@ -238,10 +260,10 @@ pub enum NodeType {
     /// assert_eq!("(1, 4):SubExpression([(1, 1):UnquotedIdentifier(\"foo\"), (1, 5):UnquotedIdentifier(\"bar\")])", format!("{:?}", ast));
     SubExpression(Vec<AST>),
 
-    /// Supports the [`NodeType::Filter`], [`NodeType::Flatten`] , [`NodeType::ListWildcard`] and [`NodeType::Slice`] projections.
+    /// Supports the [`NodeType::Filter`], [`NodeType::Flatten`] , [`NodeType::ListWildcard`], [`NodeType::Slice`] and [`NodeType::SliceExpression`] projections.
     ///
     /// A projection has three nodes:
-    /// - [`NodeType::Filter`], [`NodeType::Flatten`], [`NodeType::ListWildcard`] or [`NodeType::Slice`].
+    /// - [`NodeType::Filter`], [`NodeType::Flatten`], [`NodeType::ListWildcard`], [`NodeType::Slice`] or [`NodeType::SliceExpression`].
     /// - An optional left node.
     /// - An optional right node.
     /// # Example
@@ -255,6 +277,19 @@ pub enum NodeType {
     /// let ast = jmespath::parse("*").unwrap();
     /// assert_eq!("(1, 1):HashWildcardProjection([(0, 0):None, (0, 0):None])", format!("{:?}", ast));
     HashWildcardProjection(Vec<AST>),
+    /// The descendant `..` projection.
+    ///
+    /// A descendant projection has two nodes:
+    /// - An optional left node.
+    /// - The right node, evaluated against the left node and each object or array
+    ///   nested under it, in document order.
+    /// # Example
+    /// ```
+    /// use jmespath_community as jmespath;
+    /// use jmespath::{AST, NodeType};
+    /// let ast = jmespath::parse("foo..id").unwrap();
+    /// assert_eq!("(1, 4):DescendantProjection([(1, 1):UnquotedIdentifier(\"foo\"), (1, 6):UnquotedIdentifier(\"id\")])", format!("{:?}", ast));
+    DescendantProjection(Vec<AST>),
 
     /// A binary arithmetic-expression `left { + | − | - | × | * | ÷ | / | % | // } right`  
     /// or unary arithmetic expression `{ + | − | - } right`.
@@ -289,6 +324,17 @@ pub enum NodeType {
     /// assert_eq!("(1, 5):ComparatorExpression([(1, 1):UnquotedIdentifier(\"foo\"), (1, 5):GreaterThan, (1, 7):JsonValue(\"2\")])", format!("{:?}", ast));
     ComparatorExpression(Vec<AST>),
 
+    /// A conditional-expression `condition ? left : right`.
+    ///
+    /// Evaluates to `left` if `condition` is truthy, or to `right` otherwise.
+    /// # Example
+    /// ```
+    /// use jmespath_community as jmespath;
+    /// use jmespath::{AST, NodeType};
+    /// let ast = jmespath::parse("foo ? bar : baz").unwrap();
+    /// assert_eq!("(1, 5):ConditionalExpression([(1, 1):UnquotedIdentifier(\"foo\"), (1, 7):UnquotedIdentifier(\"bar\"), (1, 13):UnquotedIdentifier(\"baz\")])", format!("{:?}", ast));
+    ConditionalExpression(Vec<AST>),
+
     /// A function expression `avg(foo[*])`.
     /// # Example
     /// ```
@@ -305,7 +351,7 @@ pub enum NodeType {
     /// See [`NodeType::FunctionArguments`].
     FunctionArguments(Vec<AST>),
 
-    /// An index-expression `[0]` or `foo[0]`.
+    /// An index-expression `[0]`, `foo[0]` or `foo[$i]`.
     /// # Example
     /// ```
     /// use jmespath_community as jmespath;
@@ -317,6 +363,21 @@ pub enum NodeType {
     #[doc(hidden)]
     Index(Vec<AST>),
 
+    /// A lambda expression `($acc, $x) => $acc + $x`.
+    ///
+    /// A lambda expression has zero or more [`NodeType::VariableRef`] parameters,
+    /// followed by the body of the lambda expression.
+    /// Like an expression-type, it may only appear as a function argument.
+    /// It evaluates to a [`crate::Value::Expression`] that functions can invoke
+    /// with positional arguments using [`crate::FunctionContext::invoke()`].
+    /// # Example
+    /// ```
+    /// use jmespath_community as jmespath;
+    /// use jmespath::{AST, NodeType};
+    /// let ast = jmespath::parse("f(($x) => $x)").unwrap();
+    /// assert_eq!("(1, 1):FunctionExpression([(1, 1):UnquotedIdentifier(\"f\"), (1, 3):FunctionArguments([(1, 3):LambdaExpression([(1, 4):VariableRef(\"$x\"), (1, 11):VariableRef(\"$x\")])])])", format!("{:?}", ast));
+    LambdaExpression(Vec<AST>),
+
     /// A let expression `let $foo = bar in baz`.
     /// # Example
     /// ```
@@ -327,6 +388,33 @@ pub enum NodeType {
     LetExpression(Vec<AST>),
     /// Supports the [`NodeType::LetExpression`] AST node.
     LetBindings(Vec<AST>),
+    /// Supports the [`NodeType::LetExpression`] AST node for a `let*` expression,
+    /// where each binding can refer to the preceding ones.
+    /// # Example
+    /// ```
+    /// use jmespath_community as jmespath;
+    /// use jmespath::{AST, NodeType};
+    /// let ast = jmespath::parse("let* $a = foo, $b = $a in $b").unwrap();
+    /// assert_eq!("(1, 1):LetExpression([(1, 9):SequentialLetBindings([(1, 6):VariableRef(\"$a\"), (1, 11):UnquotedIdentifier(\"foo\"), (1, 16):VariableRef(\"$b\"), (1, 21):VariableRef(\"$a\")]), (1, 27):VariableRef(\"$b\")])", format!("{:?}", ast));
+    SequentialLetBindings(Vec<AST>),
+    /// A pattern `[$first, $second]` that destructures an array in a let binding.
+    /// # Example
+    /// ```
+    /// use jmespath_community as jmespath;
+    /// use jmespath::{AST, NodeType};
+    /// let ast = jmespath::parse("let [$a, $b] = @ in $b").unwrap();
+    /// assert_eq!("(1, 1):LetExpression([(1, 14):LetBindings([(1, 5):ArrayPattern([(1, 6):VariableRef(\"$a\"), (1, 10):VariableRef(\"$b\")]), (1, 16):CurrentNode]), (1, 21):VariableRef(\"$b\")])", format!("{:?}", ast));
+    ArrayPattern(Vec<AST>),
+    /// A pattern `{name: $n, id: $i}` that destructures an object in a let binding.
+    ///
+    /// Its children are pairs of keys and patterns.
+    /// # Example
+    /// ```
+    /// use jmespath_community as jmespath;
+    /// use jmespath::{AST, NodeType};
+    /// let ast = jmespath::parse("let {name: $n} = @ in $n").unwrap();
+    /// assert_eq!("(1, 1):LetExpression([(1, 16):LetBindings([(1, 5):ObjectPattern([(1, 6):UnquotedIdentifier(\"name\"), (1, 12):VariableRef(\"$n\")]), (1, 18):CurrentNode]), (1, 23):VariableRef(\"$n\")])", format!("{:?}", ast));
+    ObjectPattern(Vec<AST>),
 
     /// A unary or binary logical-expression `left { && | || } right`  
     /// or unary negative predicate `! foo`.
@@ -372,6 +460,38 @@ macro_rules! as_ {
 }
 impl NodeType {
     as_!(as_index, Index);
+
+    /// Returns the child nodes of this node, if any.
+    pub fn children(&self) -> Vec<&AST> {
+        match self {
+            Self::Filter(v)
+            | Self::Expression(v)
+            | Self::ParenExpression(v)
+            | Self::PipeExpression(v)
+            | Self::SubExpression(v)
+            | Self::Projection(v)
+            | Self::HashWildcardProjection(v)
+            | Self::DescendantProjection(v)
+            | Self::ArithmeticExpression(v)
+            | Self::ComparatorExpression(v)
+            | Self::ConditionalExpression(v)
+            | Self::FunctionExpression(v)
+            | Self::FunctionArguments(v)
+            | Self::IndexExpression(v)
+            | Self::Index(v)
+            | Self::LambdaExpression(v)
+            | Self::LetExpression(v)
+            | Self::LetBindings(v)
+            | Self::SequentialLetBindings(v)
+            | Self::ArrayPattern(v)
+            | Self::ObjectPattern(v)
+            | Self::LogicalExpression(v)
+            | Self::MultiSelectList(v)
+            | Self::SliceExpression(v) => v.iter().collect(),
+            Self::MultiSelectHash(map) => map.values().collect(),
+            _ => vec![],
+        }
+    }
 }
 
 /// Represents the parameters for a slice [`NodeType::Projection`].
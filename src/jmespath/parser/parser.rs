@@ -1,7 +1,10 @@
-use super::{AST, grammar};
+use super::recovery::parse_with_recovery;
+use super::{AST, NodeType, grammar};
 use crate::errors::Error as ParseError;
-use crate::lexer::tokenize;
-use santiago::lexer::Lexeme;
+use crate::errors::error_builder::{ErrorBuilder, SyntaxErrorBuilder};
+use crate::errors::santiago::syntax_error;
+use crate::errors::syntax::SyntaxErrorBuilderFactory;
+use crate::lexer::{SourceMap, tokenize};
 use std::rc::Rc;
 
 /// Parses a JMESPath expression and returns an [`AST`]
@@ -18,21 +21,100 @@ use std::rc::Rc;
 
 pub fn parse(input: &str) -> Result<AST, ParseError> {
     let tokens = tokenize(input)?;
-    parse_tokens(tokens)
+    let source_map = Rc::new(SourceMap::new(input));
+    let grammar = grammar::grammar(&source_map);
+    match santiago::parser::parse(&grammar, &tokens) {
+        Ok(parse_trees) => {
+            let ast = parse_trees[0].as_abstract_syntax_tree();
+            let mut errors = Vec::new();
+            check_placeholders(&ast, &mut errors);
+            match errors.into_iter().next() {
+                Some(err) => Err(err),
+                None => Ok(ast),
+            }
+        }
+        Err(err) => Err(syntax_error(&err, &tokens, &source_map, &grammar)),
+    }
+}
+
+/// Checks that the name of each placeholder immediately follows its `:`.
+///
+/// The grammar accepts a placeholder as a `:` followed by an identifier,
+/// so that `{foo: bar}` and `foo ? bar : baz` need no special lexing.
+fn check_placeholders(ast: &AST, errors: &mut Vec<ParseError>) {
+    match &ast.node_type {
+        NodeType::Placeholder(name) if ast.span.end - ast.span.start != name.len() => {
+            let reason = format!("unexpected whitespace in placeholder '{}'", name);
+            errors.push(
+                ParseError::get_syntax_error_builder()
+                    .at_span(ast.span)
+                    .for_reason(&reason)
+                    .build(),
+            );
+        }
+        node_type => {
+            for child in node_type.children() {
+                check_placeholders(child, errors);
+            }
+        }
+    }
+}
+
+/// Represents the outcome of parsing a JMESPath expression
+/// with the [`parse_with_diagnostics`] function.
+pub struct ParseDiagnostics {
+    /// The abstract syntax tree, if the expression could be tokenized.
+    ///
+    /// When syntax errors are found, this is a partial [`AST`] where the
+    /// offending parts of the expression are replaced with [`NodeType::None`](crate::NodeType::None) nodes.
+    pub ast: Option<AST>,
+    /// The syntax errors found in the expression, in order of appearance.
+    pub errors: Vec<ParseError>,
 }
-fn parse_tokens(tokens: Vec<Rc<Lexeme>>) -> Result<AST, ParseError> {
-    let grammar = grammar::grammar();
-    let parse_trees = santiago::parser::parse(&grammar, &tokens)?;
-    let parse_tree = parse_trees.get(0);
-    return match parse_tree {
-        Some(parsed) => Ok(parsed.as_abstract_syntax_tree()),
-        None => panic!("something wrong happened"),
+
+/// Parses a JMESPath expression and reports every syntax error found.
+///
+/// Unlike [`parse`], this function does not stop at the first syntax error.
+/// Instead, the parser recovers at the next `|`, `,` or closing bracket.
+///
+/// # Example
+///
+/// ```
+/// use jmespath_community as jmespath;
+/// use jmespath::parse_with_diagnostics;
+///
+/// let diagnostics = parse_with_diagnostics("foo.@ | [bar, baz.]");
+///
+/// assert!(diagnostics.ast.is_some());
+/// assert_eq!(2, diagnostics.errors.len());
+/// assert_eq!(
+///     "Error(1, 5): syntax, unexpected `@`, expected one of `*`, `[`, `{`, identifier, quoted identifier",
+///     diagnostics.errors[0].to_string()
+/// );
+/// ```
+pub fn parse_with_diagnostics(input: &str) -> ParseDiagnostics {
+    let tokens = match tokenize(input) {
+        Ok(tokens) => tokens,
+        Err(err) => {
+            return ParseDiagnostics {
+                ast: None,
+                errors: vec![err],
+            };
+        }
     };
+    let source_map = Rc::new(SourceMap::new(input));
+    let grammar = grammar::grammar(&source_map);
+    let (ast, mut errors) = parse_with_recovery(&grammar, tokens, &source_map);
+    if let Some(ast) = &ast {
+        check_placeholders(ast, &mut errors);
+    }
+    ParseDiagnostics { ast, errors }
 }
 
 #[cfg(test)]
 mod tests {
 
+    use crate::errors::{Kind, Position, Span};
     use crate::parser::{AST, NodeType, parse};
     use rstest::*;
 
@@ -42,6 +124,29 @@ mod tests {
         assert!(ast.is_err());
     }
 
+    #[test]
+    fn error_span() {
+        let err = parse("foo.@").unwrap_err();
+        assert_eq!(Some(Span::new(4, 5, 1, 5)), err.span);
+    }
+
+    #[rstest]
+    #[case("foo", Span::new(0, 3, 1, 1))]
+    #[case("  'bar'", Span::new(2, 7, 1, 3))]
+    #[case("foo.bar", Span::new(0, 7, 1, 4))]
+    #[case("foo + bar", Span::new(0, 9, 1, 5))]
+    #[case("(foo)", Span::new(0, 5, 1, 1))]
+    #[case("foo[1]", Span::new(0, 6, 1, 4))]
+    #[case("length(foo)", Span::new(0, 11, 1, 1))]
+    #[case("{foo: foo}", Span::new(0, 10, 1, 1))]
+    #[case("let $foo = foo in bar", Span::new(0, 21, 1, 1))]
+    #[case("foo[*].bar", Span::new(0, 10, 1, 4))]
+    #[case("'é' | bar", Span::new(0, 10, 1, 5))]
+    fn span(#[case] input: &str, #[case] expected: Span) {
+        let ast = parse(input).unwrap();
+        assert_eq!(expected, ast.span);
+    }
+
     #[test]
     fn current_node() {
         let ast = parse("@");
@@ -172,6 +277,173 @@ mod tests {
         ));
     }
     #[rstest]
+    #[case("foo[$i]")]
+    #[case("foo[(length(@) - `1`)]")]
+    #[case("foo.bar[$i]")]
+    fn dynamic_index_expression(#[case] input: &str) {
+        let ast = parse(input);
+        assert!(matches!(
+            ast,
+            Ok(AST {
+                node_type: NodeType::IndexExpression(..),
+                ..
+            })
+        ));
+    }
+    #[rstest]
+    #[case("[$i:]")]
+    #[case("foo[:$i]")]
+    #[case("foo[::(bar)]")]
+    #[case("foo[$i:(length(@)):1]")]
+    fn dynamic_slice(#[case] input: &str) {
+        let ast = parse(input).unwrap();
+        let NodeType::Projection(nodes) = ast.node_type else {
+            panic!("expected a projection: {}", input);
+        };
+        assert!(matches!(nodes[0].node_type, NodeType::SliceExpression(..)));
+    }
+    /// Returns the children of the lambda-expression passed to a function.
+    fn lambda_argument(input: &str) -> Vec<AST> {
+        let ast = parse(&format!("f({})", input)).unwrap();
+        let NodeType::FunctionExpression(nodes) = &ast.node_type else {
+            panic!("expected a function-expression: {}", input);
+        };
+        let NodeType::FunctionArguments(args) = &nodes[1].node_type else {
+            panic!("expected function arguments: {}", input);
+        };
+        let NodeType::LambdaExpression(nodes) = &args[0].node_type else {
+            panic!("expected a lambda-expression: {}", input);
+        };
+        nodes.clone()
+    }
+    #[rstest]
+    #[case("() => `1`", 1)]
+    #[case("($x) => $x", 2)]
+    #[case("($x, $y) => $x - $y", 3)]
+    #[case("($x) => $x ? a : b", 2)]
+    fn lambda_expression(#[case] input: &str, #[case] count: usize) {
+        assert_eq!(count, lambda_argument(input).len(), "{}", input);
+    }
+    #[rstest]
+    #[case("($x) => $x | foo", "pipe")]
+    #[case("($x, $y) => $x * $y", "star")]
+    #[case("($x) => $x[*].foo", "projection")]
+    #[case("($x) => $x[*] | foo", "pipe")]
+    #[case("($x) => $x[0] | foo", "pipe")]
+    #[case("($x, $y) => $x[*] * $y", "star")]
+    fn lambda_expression_body_extends_as_far_as_possible(#[case] input: &str, #[case] body: &str) {
+        let nodes = lambda_argument(input);
+        let matched = match nodes.last().unwrap().node_type {
+            NodeType::PipeExpression(..) => "pipe",
+            NodeType::ArithmeticExpression(..) => "star",
+            NodeType::Projection(..) => "projection",
+            _ => "other",
+        };
+        assert_eq!(body, matched, "{}", input);
+    }
+    #[test]
+    fn lambda_expression_ends_at_argument() {
+        let ast = parse("f(($x) => $x | foo, bar)").unwrap();
+        let NodeType::FunctionExpression(nodes) = &ast.node_type else {
+            panic!("expected a function-expression");
+        };
+        let NodeType::FunctionArguments(args) = &nodes[1].node_type else {
+            panic!("expected function arguments");
+        };
+        assert_eq!(2, args.len());
+    }
+    #[rstest]
+    #[case("($x) => $x")]
+    #[case("{f: ($x) => $x}")]
+    #[case("[($x) => $x]")]
+    #[case("(($x) => $x).foo")]
+    #[case("foo | ($x) => $x")]
+    #[case("f(($x) => ($y) => $y)")]
+    fn lambda_expression_only_as_function_argument(#[case] input: &str) {
+        assert_eq!(
+            crate::errors::Kind::Syntax,
+            parse(input).unwrap_err().kind,
+            "{}",
+            input
+        );
+    }
+    #[test]
+    fn comments() {
+        let ast = parse("foo # first\n  | bar # second").unwrap();
+        let NodeType::PipeExpression(nodes) = &ast.node_type else {
+            panic!("expected a pipe-expression");
+        };
+        assert_eq!((2, 5), (nodes[1].position.line, nodes[1].position.column));
+    }
+    #[test]
+    fn comments_error_position() {
+        let err = parse("foo # comment\n.@").unwrap_err();
+        assert_eq!(Some(crate::errors::Position::new(2, 2)), err.position);
+    }
+    #[rstest]
+    #[case("[$i]")]
+    #[case("[(foo)]")]
+    fn standalone_dynamic_index_is_multi_select_list(#[case] input: &str) {
+        let ast = parse(input);
+        assert!(matches!(
+            ast,
+            Ok(AST {
+                node_type: NodeType::MultiSelectList(..),
+                ..
+            })
+        ));
+    }
+    #[rstest]
+    #[case("..foo")]
+    #[case("foo..bar")]
+    #[case("foo.bar..baz")]
+    #[case("..foo.bar")]
+    #[case("..foo[0]")]
+    #[case("..foo[*].bar")]
+    #[case("..*")]
+    fn descendant_projection(#[case] input: &str) {
+        let ast = parse(input);
+        assert!(matches!(
+            ast,
+            Ok(AST {
+                node_type: NodeType::DescendantProjection(..),
+                ..
+            })
+        ));
+    }
+    #[rstest]
+    #[case("foo ? bar : baz")]
+    #[case("foo == `1` ? bar : baz")]
+    #[case("foo || bar ? baz : qux")]
+    #[case("foo ? bar : baz ? qux : quux")]
+    #[case("foo ? bar ? baz : qux : quux")]
+    fn conditional_expression(#[case] input: &str) {
+        let ast = parse(input);
+        assert!(matches!(
+            ast,
+            Ok(AST {
+                node_type: NodeType::ConditionalExpression(..),
+                ..
+            })
+        ));
+    }
+    #[rstest]
+    #[case(
+        "foo ? bar : baz | qux",
+        "PipeExpression [1, 17]\n  ConditionalExpression [1, 5]\n    UnquotedIdentifier(foo) [1, 1]\n    UnquotedIdentifier(bar) [1, 7]\n    UnquotedIdentifier(baz) [1, 13]\n  UnquotedIdentifier(qux) [1, 19]"
+    )]
+    #[case(
+        "a ? b : c ? d : e",
+        "ConditionalExpression [1, 3]\n  UnquotedIdentifier(a) [1, 1]\n  UnquotedIdentifier(b) [1, 5]\n  ConditionalExpression [1, 11]\n    UnquotedIdentifier(c) [1, 9]\n    UnquotedIdentifier(d) [1, 13]\n    UnquotedIdentifier(e) [1, 17]"
+    )]
+    #[case(
+        "a && b ? c || d : e",
+        "ConditionalExpression [1, 8]\n  LogicalExpression [1, 3]\n    UnquotedIdentifier(a) [1, 1]\n    And [1, 3]\n    UnquotedIdentifier(b) [1, 6]\n  LogicalExpression [1, 12]\n    UnquotedIdentifier(c) [1, 10]\n    Or [1, 12]\n    UnquotedIdentifier(d) [1, 15]\n  UnquotedIdentifier(e) [1, 19]"
+    )]
+    fn conditional_expression_precedence(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(expected, parse(input).unwrap().to_string());
+    }
+    #[rstest]
     #[case("custom_function()")]
     #[case("custom_function(one)")]
     #[case("custom_function(one, two)")]
@@ -218,6 +490,9 @@ mod tests {
     #[rstest]
     #[case("let $foo = foo in bar")]
     #[case("let $foo = foo, $bar = bar in baz")]
+    #[case("let* $foo = foo, $bar = $foo in baz")]
+    #[case("let [$a, $b] = foo in bar")]
+    #[case("let {a: $a, \"b c\": [$b, {d: $d}]} = foo in bar")]
     fn let_expression(#[case] input: &str) {
         let ast = parse(input);
         assert!(matches!(
@@ -361,4 +636,51 @@ mod tests {
             })
         ));
     }
+
+    #[rstest]
+    #[case(":foo")]
+    #[case("[:foo]")]
+    #[case("{a::foo}")]
+    #[case("{a: :foo}")]
+    #[case("a ? :foo:b")]
+    #[case("a ? b::foo")]
+    #[case("bar[?age > :foo]")]
+    fn placeholder(#[case] input: &str) {
+        let ast = parse(input).unwrap();
+        assert!(
+            format!("{:?}", ast).contains("Placeholder(\":foo\")"),
+            "{}",
+            input
+        );
+    }
+
+    #[rstest]
+    #[case("{a:foo}")]
+    #[case("{a:foo, b:bar}")]
+    #[case("a ? b:foo")]
+    #[case("a ? b.*:foo")]
+    #[case("a ? b[*]:foo")]
+    #[case("a ? `1`:foo")]
+    #[case("a ? 'b':foo")]
+    #[case("a ? (b):foo")]
+    fn placeholder_requires_value_position(#[case] input: &str) {
+        let ast = parse(input).unwrap();
+        assert!(!format!("{:?}", ast).contains("Placeholder"), "{}", input);
+    }
+
+    #[rstest]
+    #[case("unexpected identifier", (1, 6), "foo[:a]")]
+    #[case("unexpected identifier", (1, 7), "foo[1:a]")]
+    #[case("unexpected whitespace in placeholder ':foo'", (1, 2), "[: foo]")]
+    #[case("unexpected whitespace in placeholder ':foo'", (1, 9), "a ? b : :\nfoo")]
+    fn placeholder_errors(
+        #[case] message: &str,
+        #[case] position: (usize, usize),
+        #[case] input: &str,
+    ) {
+        let err = parse(input).unwrap_err();
+        assert_eq!(Kind::Syntax, err.kind);
+        assert!(err.message.starts_with(message), "{}", err.message);
+        assert_eq!(Some(Position::new(position.0, position.1)), err.position);
+    }
 }
@@ -0,0 +1,293 @@
+use std::rc::Rc;
+
+use santiago::{
+    grammar::Grammar,
+    lexer::{Lexeme, Position},
+};
+
+use super::AST;
+use crate::errors::Error as ParseError;
+use crate::errors::santiago::{failing_index, syntax_error};
+use crate::lexer::SourceMap;
+
+/// The kind of the lexeme that stands for an expression
+/// that has been discarded during error recovery.
+pub(crate) const MISSING: &str = "missing";
+
+const SEPARATORS: [&str; 5] = ["assign", "colon", "comma", "in", "pipe"];
+
+/// Returns the kind of the closing bracket matching an opening bracket.
+fn closing(kind: &str) -> Option<&'static str> {
+    match kind {
+        "lbracket" | "filter" => Some("rbracket"),
+        "lbrace" => Some("rbrace"),
+        "lparen" => Some("rparen"),
+        _ => None,
+    }
+}
+fn is_opening(kind: &str) -> bool {
+    closing(kind).is_some()
+}
+fn is_closing(kind: &str) -> bool {
+    matches!(kind, "rbracket" | "rbrace" | "rparen")
+}
+fn is_separator(kind: &str) -> bool {
+    SEPARATORS.contains(&kind)
+}
+
+/// Parses a sequence of tokens, recovering from syntax errors.
+///
+/// Whenever the parser fails, the error is recorded and the tokens
+/// are repaired so that parsing can resume past the error:
+///
+/// * tokens up to the next `|`, `,` or closing bracket are replaced
+///   with a placeholder expression that evaluates to [`NodeType::None`](super::NodeType::None).
+/// * mismatched closing brackets are replaced with the expected ones,
+///   or preceded by them when they close an outer bracket.
+/// * missing closing brackets are added at the end of the expression.
+///
+/// Returns a partial [`AST`] as well as every syntax error found.
+pub(crate) fn parse_with_recovery(
+    grammar: &Grammar<AST>,
+    tokens: Vec<Rc<Lexeme>>,
+    source_map: &SourceMap,
+) -> (Option<AST>, Vec<ParseError>) {
+    let limit = 4 * tokens.len() + 16;
+    let mut recovery = Recovery::new(tokens, source_map);
+    let mut errors: Vec<ParseError> = Vec::new();
+    for _ in 0..limit {
+        let error = match santiago::parser::parse(grammar, &recovery.tokens) {
+            Ok(trees) => return (Some(trees[0].as_abstract_syntax_tree()), errors),
+            Err(error) => error,
+        };
+        let index = failing_index(&error, &recovery.tokens);
+        if !recovery.is_synthetic(index) {
+            let error = syntax_error(&error, &recovery.tokens, source_map, grammar);
+            if !errors.iter().any(|e| e.position == error.position) {
+                errors.push(error);
+            }
+        }
+        recovery.repair(index);
+    }
+    (None, errors)
+}
+
+struct Recovery<'a> {
+    tokens: Vec<Rc<Lexeme>>,
+    synthetic: Vec<bool>,
+    source_map: &'a SourceMap,
+}
+impl<'a> Recovery<'a> {
+    fn new(tokens: Vec<Rc<Lexeme>>, source_map: &'a SourceMap) -> Self {
+        let synthetic = vec![false; tokens.len()];
+        Recovery {
+            tokens,
+            synthetic,
+            source_map,
+        }
+    }
+    fn is_synthetic(&self, index: usize) -> bool {
+        self.synthetic.get(index).copied().unwrap_or(false)
+    }
+    fn kind(&self, index: usize) -> &str {
+        &self.tokens[index].kind
+    }
+    /// Repairs the tokens so that the parser can make progress
+    /// past the token at the specified index.
+    fn repair(&mut self, index: usize) {
+        let unclosed = self.unclosed(index);
+        if index == self.tokens.len() && !unclosed.is_empty() {
+            let position = self.position(index);
+            for opening in unclosed.iter().rev() {
+                let kind = closing(self.kind(*opening)).unwrap();
+                let token = Self::make_token(kind, position.clone());
+                self.replace(index..index, Some(token));
+            }
+            return;
+        }
+        if index < self.tokens.len() && is_closing(self.kind(index)) {
+            match unclosed.last().map(|opening| closing(self.kind(*opening))) {
+                None => {
+                    // remove extraneous closing bracket
+                    self.replace(index..index + 1, None);
+                    return;
+                }
+                Some(Some(kind)) if kind != self.kind(index) => {
+                    // close the innermost bracket if the closing bracket
+                    // matches an outer one, otherwise replace it
+                    let outer = unclosed
+                        .iter()
+                        .any(|opening| closing(self.kind(*opening)) == Some(self.kind(index)));
+                    let end = if outer { index } else { index + 1 };
+                    let token = Self::make_token(kind, self.position(index));
+                    self.replace(index..end, Some(token));
+                    return;
+                }
+                _ => {}
+            }
+        }
+        let start = self.segment_start(index);
+        let end = self.segment_end(index);
+        let no_progress = start < end && (start..end).all(|i| self.synthetic[i]);
+        if no_progress {
+            self.widen(start, end);
+        } else {
+            self.replace_with_missing(start, end);
+        }
+    }
+    /// Replaces the innermost bracketed expression that
+    /// encloses the specified range with a placeholder.
+    fn widen(&mut self, start: usize, end: usize) {
+        match self.unclosed(start).last() {
+            Some(opening) => {
+                let opening = *opening;
+                let mut depth = 0;
+                let mut closing = self.tokens.len();
+                for i in opening + 1..self.tokens.len() {
+                    let kind = self.kind(i);
+                    if is_opening(kind) {
+                        depth += 1;
+                    } else if is_closing(kind) {
+                        if depth == 0 {
+                            closing = i + 1;
+                            break;
+                        }
+                        depth -= 1;
+                    }
+                }
+                self.replace_with_missing(opening, std::cmp::max(closing, end));
+            }
+            None => self.replace_with_missing(0, self.tokens.len()),
+        }
+    }
+    fn replace_with_missing(&mut self, start: usize, end: usize) {
+        let token = Self::make_token(MISSING, self.position(start));
+        self.replace(start..end, Some(token));
+    }
+    fn replace(&mut self, range: std::ops::Range<usize>, token: Option<Rc<Lexeme>>) {
+        let synthetic = token.iter().map(|_| true).collect::<Vec<bool>>();
+        self.synthetic.splice(range.clone(), synthetic);
+        self.tokens.splice(range, token);
+    }
+    fn make_token(kind: &str, position: Position) -> Rc<Lexeme> {
+        Rc::new(Lexeme {
+            kind: kind.to_string(),
+            raw: "".to_string(),
+            position,
+        })
+    }
+    /// Returns the position of the token at the specified index,
+    /// or the end of the expression.
+    fn position(&self, index: usize) -> Position {
+        match self.tokens.get(index) {
+            Some(token) => token.position.clone(),
+            None => {
+                let end = self.source_map.end();
+                Position {
+                    line: end.line,
+                    column: end.column,
+                }
+            }
+        }
+    }
+    /// Returns the indices of the brackets that are
+    /// still open before the specified index.
+    fn unclosed(&self, index: usize) -> Vec<usize> {
+        let mut stack: Vec<usize> = Vec::new();
+        for i in 0..index {
+            let kind = self.kind(i);
+            if is_opening(kind) {
+                stack.push(i);
+            } else if is_closing(kind) {
+                stack.pop();
+            }
+        }
+        stack
+    }
+    /// Returns the index of the first token of the segment
+    /// that contains the token at the specified index.
+    fn segment_start(&self, index: usize) -> usize {
+        let mut depth = 0;
+        for i in (0..index).rev() {
+            let kind = self.kind(i);
+            if is_closing(kind) {
+                depth += 1;
+            } else if is_opening(kind) {
+                if depth == 0 {
+                    return i + 1;
+                }
+                depth -= 1;
+            } else if is_separator(kind) && depth == 0 {
+                return i + 1;
+            }
+        }
+        0
+    }
+    /// Returns the index past the last token of the segment
+    /// that contains the token at the specified index.
+    fn segment_end(&self, index: usize) -> usize {
+        let mut depth = 0;
+        for i in index..self.tokens.len() {
+            let kind = self.kind(i);
+            if is_opening(kind) {
+                depth += 1;
+            } else if is_closing(kind) {
+                if depth == 0 {
+                    return i;
+                }
+                depth -= 1;
+            } else if is_separator(kind) && depth == 0 {
+                return i;
+            }
+        }
+        self.tokens.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::Position;
+    use crate::parser::parse_with_diagnostics;
+    use rstest::*;
+
+    #[rstest]
+    #[case("foo.bar | baz", vec![])]
+    #[case("foo.@ | bar", vec![(1, 5)])]
+    #[case("foo.@ | bar.[ | baz", vec![(1, 5), (1, 15), (1, 20)])]
+    #[case("[foo., bar.@, baz]", vec![(1, 6), (1, 12)])]
+    #[case("{a: foo., b: bar[0}", vec![(1, 9), (1, 19)])]
+    #[case("foo[0", vec![(1, 6)])]
+    #[case("foo |", vec![(1, 6)])]
+    #[case("foo)", vec![(1, 4)])]
+    #[case("length(foo., @.@)", vec![(1, 12), (1, 16)])]
+    fn parse_with_diagnostics_errors(#[case] input: &str, #[case] expected: Vec<(usize, usize)>) {
+        let diagnostics = parse_with_diagnostics(input);
+        let positions: Vec<Position> = diagnostics
+            .errors
+            .iter()
+            .map(|err| err.position.unwrap())
+            .collect();
+        let expected: Vec<Position> = expected
+            .iter()
+            .map(|(line, column)| Position::new(*line, *column))
+            .collect();
+        assert_eq!(expected, positions);
+        assert!(diagnostics.ast.is_some());
+    }
+
+    #[test]
+    fn parse_with_diagnostics_partial_ast() {
+        let diagnostics = parse_with_diagnostics("foo.@ | bar");
+        assert_eq!(
+            "PipeExpression [1, 7]\n  None [1, 1]\n  UnquotedIdentifier(bar) [1, 9]",
+            diagnostics.ast.unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn parse_with_diagnostics_lexer_error() {
+        let diagnostics = parse_with_diagnostics("foo | 'bar");
+        assert!(diagnostics.ast.is_none());
+        assert_eq!(1, diagnostics.errors.len());
+    }
+}
@@ -0,0 +1,271 @@
+use std::fmt;
+use std::rc::Rc;
+
+use crate::Value;
+
+/// Represents a step from a JSON node to one of its children.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PathSegment {
+    /// Selects the member of an object with the specified key.
+    Key(String),
+    /// Selects the element of an array at the specified zero-based index.
+    Index(usize),
+}
+
+/// Represents the location of a value in the JSON document being searched.
+///
+/// A path renders as a JMESPath expression that selects the value, or
+/// as a [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901) using
+/// [`Path::to_json_pointer()`].
+///
+/// # Example
+/// ```
+/// use jmespath_community as jmespath;
+/// use jmespath::{Path, PathSegment};
+///
+/// let path = Path::new(vec![
+///     PathSegment::Key("people".to_string()),
+///     PathSegment::Index(2),
+///     PathSegment::Key("first name".to_string()),
+/// ]);
+/// assert_eq!(r#"people[2]."first name""#, path.to_string());
+/// assert_eq!("/people/2/first name", path.to_json_pointer());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Path {
+    segments: Vec<PathSegment>,
+}
+impl Path {
+    /// Creates a new [`Path`] from its segments, starting from the root.
+    pub fn new(segments: Vec<PathSegment>) -> Self {
+        Path { segments }
+    }
+    /// Returns the segments of the path, starting from the root.
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+    /// Returns `true` if the path refers to the root of the document.
+    pub fn is_root(&self) -> bool {
+        self.segments.is_empty()
+    }
+    /// Returns the value at this location in the specified document, if any.
+    pub fn resolve<'v>(&self, root: &'v Value) -> Option<&'v Value> {
+        let mut value = root;
+        for segment in &self.segments {
+            value = match (segment, value) {
+                (PathSegment::Key(key), Value::Object(map)) => map.get(key)?,
+                (PathSegment::Index(index), Value::Array(array)) => array.get(*index)?,
+                _ => return None,
+            };
+        }
+        Some(value)
+    }
+    /// Returns a mutable reference to the value at this location
+    /// in the specified document, if any.
+    pub fn resolve_mut<'v>(&self, root: &'v mut Value) -> Option<&'v mut Value> {
+        let mut value = root;
+        for segment in &self.segments {
+            value = match (segment, value) {
+                (PathSegment::Key(key), Value::Object(map)) => map.get_mut(key)?,
+                (PathSegment::Index(index), Value::Array(array)) => array.get_mut(*index)?,
+                _ => return None,
+            };
+        }
+        Some(value)
+    }
+    /// Removes the value at this location from the specified document.
+    ///
+    /// Returns the removed value, or `None` if the location does not exist
+    /// or refers to the root of the document.
+    pub(crate) fn remove(&self, root: &mut Value) -> Option<Value> {
+        let (last, parent) = self.segments.split_last()?;
+        let parent = Path::new(parent.to_vec()).resolve_mut(root)?;
+        match (last, parent) {
+            #[cfg(feature = "preserve_order")]
+            (PathSegment::Key(key), Value::Object(map)) => map.shift_remove(key),
+            #[cfg(not(feature = "preserve_order"))]
+            (PathSegment::Key(key), Value::Object(map)) => map.remove(key),
+            (PathSegment::Index(index), Value::Array(array)) if *index < array.len() => {
+                Some(array.remove(*index))
+            }
+            _ => None,
+        }
+    }
+    /// Renders the path as a JSON Pointer.
+    ///
+    /// The root of the document is the empty string.
+    pub fn to_json_pointer(&self) -> String {
+        let mut pointer = String::new();
+        for segment in &self.segments {
+            pointer.push('/');
+            match segment {
+                PathSegment::Key(key) => {
+                    pointer.push_str(&key.replace('~', "~0").replace('/', "~1"))
+                }
+                PathSegment::Index(index) => pointer.push_str(&index.to_string()),
+            }
+        }
+        pointer
+    }
+}
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.segments.is_empty() {
+            return write!(f, "@");
+        }
+        for (position, segment) in self.segments.iter().enumerate() {
+            match segment {
+                PathSegment::Key(key) => {
+                    if position > 0 {
+                        write!(f, ".")?;
+                    }
+                    if is_unquoted_identifier(key) {
+                        write!(f, "{}", key)?;
+                    } else {
+                        write!(f, "{}", Value::String(key.clone()).to_json())?;
+                    }
+                }
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+        Ok(())
+    }
+}
+/// Returns `true` if the key can be written as an unquoted identifier.
+pub(crate) fn is_unquoted_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    let starts_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+    starts_identifier
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !matches!(key, "let" | "in")
+}
+
+/// Represents a result of a JMESPath expression along with its location.
+///
+/// Values taken directly from the JSON document have a [`Path`].
+/// Values constructed while evaluating the expression, _e.g_ by
+/// a function, a multi-select or a literal, are _synthetic_ and have none.
+///
+/// See [`crate::search_paths()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Located {
+    /// The value.
+    pub value: Value,
+    /// The location of the value in the JSON document, if any.
+    pub path: Option<Path>,
+}
+impl Located {
+    /// Returns `true` if the value has been constructed by the expression
+    /// rather than taken from the JSON document.
+    pub fn is_synthetic(&self) -> bool {
+        self.path.is_none()
+    }
+}
+
+/// Records the location of a node while evaluating an expression.
+///
+/// Each step shares the trail of its parent, so that
+/// following a child only costs a single allocation.
+#[derive(Clone)]
+pub(crate) enum Trail {
+    /// Locations are not being tracked.
+    Untracked,
+    Root,
+    Step(Rc<(Trail, PathSegment)>),
+}
+impl Trail {
+    /// Creates the trail of the root node.
+    pub fn root(tracked: bool) -> Self {
+        if tracked {
+            Trail::Root
+        } else {
+            Trail::Untracked
+        }
+    }
+    /// Extends the trail to a child node.
+    ///
+    /// The segment is only built if locations are being tracked.
+    pub fn push(&self, segment: impl FnOnce() -> PathSegment) -> Self {
+        match self {
+            Trail::Untracked => Trail::Untracked,
+            _ => Trail::Step(Rc::new((self.clone(), segment()))),
+        }
+    }
+    /// Returns the path that this trail records, if any.
+    pub fn to_path(&self) -> Option<Path> {
+        let mut segments = Vec::new();
+        let mut trail = self;
+        loop {
+            match trail {
+                Trail::Untracked => return None,
+                Trail::Root => break,
+                Trail::Step(step) => {
+                    segments.push(step.1.clone());
+                    trail = &step.0;
+                }
+            }
+        }
+        segments.reverse();
+        Some(Path::new(segments))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn key(key: &str) -> PathSegment {
+        PathSegment::Key(key.to_string())
+    }
+
+    #[rstest]
+    #[case(vec![], "@", "")]
+    #[case(vec![key("foo")], "foo", "/foo")]
+    #[case(vec![PathSegment::Index(0), key("foo")], "[0].foo", "/0/foo")]
+    #[case(vec![key("a/b"), key("c~d")], r#""a/b"."c~d""#, "/a~1b/c~0d")]
+    #[case(vec![key("let"), key("_x1")], r#""let"._x1"#, "/let/_x1")]
+    #[case(vec![key(""), PathSegment::Index(12)], r#"""[12]"#, "//12")]
+    fn it_renders_paths(
+        #[case] segments: Vec<PathSegment>,
+        #[case] expression: &str,
+        #[case] pointer: &str,
+    ) {
+        let path = Path::new(segments);
+        assert_eq!(expression, path.to_string());
+        assert_eq!(pointer, path.to_json_pointer());
+    }
+
+    #[test]
+    fn it_resolves_and_removes_values() {
+        let mut root = Value::from_json(r#"{"foo": [1, {"bar": 2}]}"#).unwrap();
+        let path = Path::new(vec![key("foo"), PathSegment::Index(1), key("bar")]);
+        assert_eq!(Some(&Value::from(2)), path.resolve(&root));
+
+        *path.resolve_mut(&mut root).unwrap() = "baz".into();
+        assert_eq!(Some(Value::from("baz")), path.remove(&mut root));
+        assert_eq!(None, path.resolve(&root));
+        assert_eq!(None, path.remove(&mut root));
+        assert_eq!(None, Path::default().remove(&mut root));
+        assert_eq!(Value::from_json(r#"{"foo": [1, {}]}"#).unwrap(), root);
+    }
+
+    #[test]
+    fn it_records_trails() {
+        let root = Trail::root(true);
+        let trail = root.push(|| key("people")).push(|| PathSegment::Index(2));
+        assert_eq!(
+            Some(Path::new(vec![key("people"), PathSegment::Index(2)])),
+            trail.to_path()
+        );
+        assert_eq!(Some(Path::default()), root.to_path());
+    }
+
+    #[test]
+    fn it_does_not_record_untracked_trails() {
+        let trail = Trail::root(false).push(|| unreachable!());
+        assert_eq!(None, trail.to_path());
+    }
+}
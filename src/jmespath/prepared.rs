@@ -0,0 +1,236 @@
+use std::sync::Arc;
+
+use crate::errors::error_builder::{ErrorBuilder, SyntaxErrorBuilder};
+use crate::errors::syntax::SyntaxErrorBuilderFactory;
+use crate::errors::{Error as RuntimeError, Kind, Span};
+use crate::functions::ReturnValue;
+use crate::interpreter::Interpreter;
+use crate::{AST, Map, NodeType, Runtime, Value, parse};
+
+/// Represents a JMESPath expression with placeholders, such as `:min_age`,
+/// whose values are bound before searching.
+///
+/// Placeholders may only stand for values, never for field names.
+/// Bound values are used as is and never parsed as part of the expression,
+/// which rules out injection through string interpolation.
+///
+/// Cloning a prepared expression does not copy its syntax tree,
+/// so that an expression can be parsed once and bound for each search.
+///
+/// # Example
+/// ```
+/// use jmespath_community as jmespath;
+/// use jmespath::{Prepared, Value};
+///
+/// let prepared = Prepared::parse("people[?age > :min_age && dept == :dept].name").unwrap();
+///
+/// let data = Value::from_json(r#"{"people": [
+///     {"name": "Ada", "age": 36, "dept": "eng"},
+///     {"name": "Alan", "age": 41, "dept": "ops"}
+/// ]}"#).unwrap();
+///
+/// let mut query = prepared.clone();
+/// query.bind("min_age", 30).unwrap().bind(":dept", "eng").unwrap();
+/// assert_eq!(Value::from_json(r#"["Ada"]"#).unwrap(), query.search(&data).unwrap());
+///
+/// // every placeholder must be bound
+/// assert!(prepared.search(&data).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Prepared {
+    ast: Arc<AST>,
+    /// The placeholders in order of first appearance, along with their location.
+    placeholders: Arc<Vec<(String, Span)>>,
+    bindings: Map<String, Value>,
+}
+impl Prepared {
+    /// Parses a JMESPath expression with placeholders.
+    pub fn parse(expression: &str) -> Result<Self, RuntimeError> {
+        Ok(parse(expression)?.into())
+    }
+    /// Returns the names of the placeholders, including their leading `:`,
+    /// in order of first appearance.
+    pub fn placeholders(&self) -> impl Iterator<Item = &str> {
+        self.placeholders.iter().map(|(name, _)| name.as_str())
+    }
+    /// Binds a value to a placeholder, with or without its leading `:`.
+    ///
+    /// Binding a name that does not appear in the expression raises an
+    /// [`Error`](RuntimeError) of kind [`Kind::InvalidValue`].
+    pub fn bind(&mut self, name: &str, value: impl Into<Value>) -> Result<&mut Self, RuntimeError> {
+        let name = format!(":{}", name.strip_prefix(':').unwrap_or(name));
+        if !self.placeholders().any(|placeholder| placeholder == name) {
+            let reason = format!("the expression has no placeholder '{}'", name);
+            return Err(RuntimeError::get_syntax_error_builder()
+                .set_kind(Kind::InvalidValue)
+                .for_reason(&reason)
+                .build());
+        }
+        self.bindings.insert(name, value.into());
+        Ok(self)
+    }
+    /// Evaluates the expression with the builtin functions.
+    ///
+    /// Every placeholder must have been bound, otherwise an
+    /// [`Error`](RuntimeError) of kind [`Kind::UndefinedVariable`] is raised.
+    ///
+    /// See [`Runtime::search_prepared()`].
+    pub fn search(&self, root: &Value) -> ReturnValue {
+        Runtime::get_shared_runtime().search_prepared(self, root)
+    }
+    pub(crate) fn evaluate(&self, runtime: &Runtime, root: &Value) -> ReturnValue {
+        let unbound = self
+            .placeholders
+            .iter()
+            .find(|(name, _)| !self.bindings.contains_key(name));
+        if let Some((name, span)) = unbound {
+            return Err(unbound_placeholder(name, Some(*span)));
+        }
+        Interpreter::new(runtime, root)
+            .with_scope(&self.bindings)
+            .evaluate(&self.ast)
+    }
+}
+
+impl From<AST> for Prepared {
+    fn from(ast: AST) -> Self {
+        let mut placeholders = Vec::new();
+        collect_placeholders(&ast, &mut placeholders);
+        Prepared {
+            ast: Arc::new(ast),
+            placeholders: Arc::new(placeholders),
+            bindings: Map::new(),
+        }
+    }
+}
+
+/// Raises an error for a placeholder that has not been bound.
+pub(crate) fn unbound_placeholder(name: &str, span: Option<Span>) -> RuntimeError {
+    let reason = format!("the placeholder '{}' is not bound", name);
+    let mut builder = RuntimeError::get_syntax_error_builder();
+    builder
+        .set_kind(Kind::UndefinedVariable)
+        .for_reason(&reason);
+    if let Some(span) = span {
+        builder.at_span(span);
+    }
+    builder.build()
+}
+
+fn collect_placeholders(ast: &AST, placeholders: &mut Vec<(String, Span)>) {
+    match &ast.node_type {
+        NodeType::Placeholder(name) => {
+            if !placeholders
+                .iter()
+                .any(|(placeholder, _)| placeholder == name)
+            {
+                placeholders.push((name.clone(), ast.span));
+            }
+        }
+        node_type => {
+            for child in node_type.children() {
+                collect_placeholders(child, placeholders);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Expr;
+    use crate::errors::Position;
+    use rstest::*;
+
+    const DOCUMENT: &str = r#"{"people": [
+        {"name": "Ada", "age": 36, "dept": "eng"},
+        {"name": "Alan", "age": 41, "dept": "ops"},
+        {"name": "Grace", "age": 45, "dept": "eng"}
+    ]}"#;
+
+    #[rstest]
+    #[case("people[?age > :min_age && dept == :dept].name", r#"["Grace"]"#)]
+    #[case("people[?dept == :dept] | length(@)", "2")]
+    #[case("{min: :min_age, dept: :dept}", r#"{"min": 40, "dept": "eng"}"#)]
+    #[case("people[?age > :min_age].{n:name} | [0]", r#"{"n": "Alan"}"#)]
+    #[case("people[0].age > :min_age ? 'older':'younger'", r#""younger""#)]
+    #[case(
+        "let $dept = 'ops' in people[?dept == $dept || age > :min_age].name",
+        r#"["Alan", "Grace"]"#
+    )]
+    fn it_searches_with_placeholders(#[case] expression: &str, #[case] expected: &str) {
+        let root = Value::from_json(DOCUMENT).unwrap();
+        let mut prepared = Prepared::parse(expression).unwrap();
+        for name in prepared
+            .placeholders()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+        {
+            let value = match name.as_str() {
+                ":min_age" => Value::from(40),
+                _ => Value::from("eng"),
+            };
+            prepared.bind(&name, value).unwrap();
+        }
+        assert_eq!(
+            Value::from_json(expected).unwrap(),
+            prepared.search(&root).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_does_not_interpret_bound_values() {
+        let root = Value::from_json(DOCUMENT).unwrap();
+        let mut prepared = Prepared::parse("people[?dept == :dept].name").unwrap();
+        prepared.bind("dept", "eng' || dept != '").unwrap();
+        assert_eq!(
+            Value::from_json("[]").unwrap(),
+            prepared.search(&root).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_lists_placeholders() {
+        let prepared = Prepared::parse("[:b, :a, :b, $c]").unwrap();
+        assert_eq!(
+            vec![":b", ":a"],
+            prepared.placeholders().collect::<Vec<_>>()
+        );
+
+        let ast = Expr::field("age").gt(Expr::placeholder("min")).build();
+        let prepared = Prepared::from(ast);
+        assert_eq!(vec![":min"], prepared.placeholders().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn it_requires_every_placeholder_to_be_bound() {
+        let mut prepared = Prepared::parse("[:a, :b]").unwrap();
+        prepared.bind(":a", 1).unwrap();
+        let err = prepared.search(&Value::Null).unwrap_err();
+        assert_eq!(Kind::UndefinedVariable, err.kind);
+        assert!(err.message.contains("':b' is not bound"), "{}", err.message);
+        assert_eq!(Some(Position::new(1, 6)), err.position);
+    }
+
+    #[test]
+    fn it_rejects_unknown_placeholders() {
+        let mut prepared = Prepared::parse(":a").unwrap();
+        let err = prepared.bind("b", 1).map(|_| ()).unwrap_err();
+        assert_eq!(Kind::InvalidValue, err.kind);
+    }
+
+    #[test]
+    fn it_reports_unbound_placeholders_when_searching() {
+        let err = crate::search("foo || :a", &Value::Null).unwrap_err();
+        assert_eq!(Kind::UndefinedVariable, err.kind);
+        assert_eq!(Some(Position::new(1, 8)), err.position);
+    }
+
+    #[rstest]
+    #[case("foo.:bar")]
+    #[case("{:key: foo}")]
+    #[case("let :a = `1` in :a")]
+    fn it_only_allows_placeholders_as_values(#[case] expression: &str) {
+        assert_eq!(Kind::Syntax, Prepared::parse(expression).unwrap_err().kind);
+    }
+}
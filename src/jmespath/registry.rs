@@ -35,17 +35,27 @@ impl Registry {
         register!(self, find_first);
         register!(self, find_last);
         register!(self, floor);
+        register!(self, fold);
         register!(self, from_items);
+        register!(self, get);
         register!(self, items);
         register!(self, keys);
         register!(self, length);
         register!(self, min_by);
         register!(self, pad_left);
         register!(self, pad_right);
+        register!(self, reduce);
         register!(self, reverse);
+        register!(self, scan);
         register!(self, starts_with);
     }
 }
+impl Registry {
+    /// Returns the names of all registered functions.
+    pub(crate) fn function_names(&self) -> impl Iterator<Item = &str> {
+        self.functions.keys().map(|name| name.as_str())
+    }
+}
 impl FunctionRegistrar for Registry {
     fn register(&mut self, func: Box<JmesPathFunction>) {
         let name = func.get_name().to_string();
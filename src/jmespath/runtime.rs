@@ -1,11 +1,16 @@
+use crate::batch;
 use crate::errors::Error as RuntimeError;
+use crate::errors::diagnostic::Diagnostic;
 use crate::errors::error_builder::{ErrorBuilder, FunctionErrorBuilder, InvalidTypeErrorBuilder};
 use crate::errors::invalid_type::InvalidTypeErrorBuilderFactory;
+use crate::functions::UserFunction;
 use crate::functions::{DataType, Function, ParamTypes, Parameter, Signature};
 use crate::interpreter::Interpreter;
+use crate::mutation;
 use crate::registry::{REGISTRY, Registry};
-use crate::{AST, Value, functions::ReturnValue};
-use crate::{JmesPathFunction, parse};
+use crate::scopes::variable_ref;
+use crate::{AST, Map, Value, functions::ReturnValue};
+use crate::{JmesPathFunction, JsonLike, Located, Prepared, parse};
 
 /// Represents an expression type as runtime.
 ///
@@ -70,6 +75,59 @@ pub trait FunctionContext {
         function: &'a dyn Function,
         param_index: usize,
     ) -> Result<ByFunctionHolder<'a>, RuntimeError>;
+    /// Invokes an expression-type with the specified positional arguments.
+    ///
+    /// When the expression-type is a lambda expression, such as `($acc, $x) => $acc + $x`,
+    /// the arguments are bound to its parameters in order. Missing arguments are
+    /// bound to `null` and extra arguments are ignored. In all cases, the current node
+    /// `@` refers to the first argument, so that expression references such as `&foo`
+    /// are evaluated against it.
+    ///
+    /// # Example
+    /// ```
+    /// use jmespath_community as jmespath;
+    /// use jmespath::{function, FunctionContext, FunctionRegistrar, Runtime, Value};
+    /// use jmespath::functions::{DataType, Function, ParamTypes::*, Parameter::{self, *}, ReturnValue};
+    ///
+    /// function!(apply, [
+    ///     f => Required(Of(DataType::ExpRef)),
+    ///     left => Required(Of(DataType::Any)),
+    ///     right => Required(Of(DataType::Any))
+    ///     ], |_: &apply, args: &Vec<Value>, context: &dyn FunctionContext| {
+    ///         let ast = args[0].as_expref().unwrap();
+    ///         context.invoke(ast, &args[1..])
+    ///     }
+    /// );
+    ///
+    /// let mut runtime = Runtime::create_runtime();
+    /// runtime.register(Box::new(apply::new()));
+    ///
+    /// let result = runtime.search("apply(($x, $y) => $x - $y, `5`, `3`)", &Value::Null).unwrap();
+    /// assert_eq!(2, result);
+    /// ```
+    fn invoke(&self, ast: &AST, args: &[Value]) -> ReturnValue;
+    /// Invokes an expression like [`FunctionContext::invoke()`] with additional
+    /// variables in scope.
+    ///
+    /// The bindings are added to the variables already in scope at the call site,
+    /// and are shadowed by the parameters of a lambda expression. This lets
+    /// functions such as [`reduce`](crate::functions::builtin::reduce) expose
+    /// state such as `$acc` to an expression reference.
+    fn invoke_with_bindings(
+        &self,
+        ast: &AST,
+        args: &[Value],
+        bindings: &Map<String, Value>,
+    ) -> ReturnValue;
+    /// Evaluates a JMESPath expression with the specified variable bindings.
+    ///
+    /// The bindings are the only variables in scope. The current node `@`
+    /// evaluates to `null` and the root node `$` refers to the JSON document
+    /// being searched.
+    ///
+    /// This supports functions whose implementation is itself a JMESPath
+    /// expression, such as [`crate::functions::UserFunction`].
+    fn evaluate_with_bindings(&self, ast: &AST, bindings: &Map<String, Value>) -> ReturnValue;
 }
 
 /// A type that represents a registry of JMESPath functions.
@@ -84,6 +142,7 @@ pub trait FunctionRegistrar {
 pub struct Runtime {
     shared_registry: &'static Box<Registry>,
     registry: Option<Box<Registry>>,
+    globals: Map<String, Value>,
 }
 impl Runtime {
     /// Returns a static shared [`Runtime`] with all builtin [`Function`]
@@ -92,6 +151,7 @@ impl Runtime {
         Runtime {
             shared_registry: &REGISTRY,
             registry: None,
+            globals: Map::new(),
         }
     }
     /// Create a new instance of the [`Runtime`] type with all
@@ -103,6 +163,7 @@ impl Runtime {
         Runtime {
             shared_registry: &REGISTRY,
             registry: Some(Box::new(Registry::create_registry())),
+            globals: Map::new(),
         }
     }
     /// Parses and evaluate a JMESPath expression.
@@ -110,11 +171,164 @@ impl Runtime {
         let ast = parse(expression)?;
         self.search_ast(&ast, root)
     }
+    /// Parses and evaluate a JMESPath expression with the specified variables in scope.
+    ///
+    /// Variable names may omit their leading `$`. The bindings shadow
+    /// global variables and are shadowed by `let` expressions. Unlike global
+    /// variables, they are not in scope in the body of functions defined
+    /// with [`Runtime::define()`].
+    ///
+    /// See [`crate::search_with()`].
+    pub fn search_with(
+        &self,
+        expression: &str,
+        root: &Value,
+        bindings: Map<String, Value>,
+    ) -> ReturnValue {
+        let ast = parse(expression)?;
+        let bindings = bindings
+            .into_iter()
+            .map(|(name, value)| (variable_ref(&name), value))
+            .collect();
+        Interpreter::new(self, root)
+            .with_scope(&bindings)
+            .evaluate(&ast)
+    }
+    /// Parses and evaluate a JMESPath expression against any serializable type
+    /// and deserializes the result into the type `R`.
+    ///
+    /// See [`crate::search_as()`].
+    pub fn search_as<R: serde::de::DeserializeOwned>(
+        &self,
+        expression: &str,
+        root: &(impl serde::Serialize + ?Sized),
+    ) -> Result<R, RuntimeError> {
+        let root = Value::from_serialize(root)?;
+        self.search(expression, &root)?.deserialize_into()
+    }
+    /// Parses and evaluate a JMESPath expression against a document
+    /// of any type that implements [`JsonLike`].
+    ///
+    /// See [`crate::search_in()`].
+    pub fn search_in<J: JsonLike>(&self, expression: &str, root: &J) -> ReturnValue {
+        let ast = parse(expression)?;
+        Interpreter::new(self, root).evaluate(&ast)
+    }
+    /// Parses and evaluate a JMESPath expression and pairs each result
+    /// with its location in the JSON document.
+    ///
+    /// See [`crate::search_paths()`].
+    pub fn search_paths<J: JsonLike>(
+        &self,
+        expression: &str,
+        root: &J,
+    ) -> Result<Vec<Located>, RuntimeError> {
+        let ast = parse(expression)?;
+        Interpreter::new(self, root)
+            .tracking_paths()
+            .evaluate_paths(&ast)
+    }
+    /// Evaluates a prepared JMESPath expression once all its placeholders are bound.
+    ///
+    /// See [`Prepared`].
+    pub fn search_prepared(&self, prepared: &Prepared, root: &Value) -> ReturnValue {
+        prepared.evaluate(self, root)
+    }
+    /// Evaluates several parsed JMESPath expressions against the same document.
+    ///
+    /// Returns one result per expression, in order.
+    ///
+    /// See [`crate::search_many()`].
+    pub fn search_many(&self, asts: &[AST], root: &Value) -> Vec<ReturnValue> {
+        batch::search_many(self, asts, root)
+    }
+    /// Updates the values selected by a JMESPath expression in place.
+    ///
+    /// See [`crate::update()`].
+    pub fn update(
+        &self,
+        root: &mut Value,
+        expression: &str,
+        f: impl FnMut(&Value) -> Value,
+    ) -> Result<usize, RuntimeError> {
+        let ast = parse(expression)?;
+        mutation::update(self, root, &ast, f)
+    }
+    /// Deletes the values selected by a JMESPath expression in place.
+    ///
+    /// See [`crate::delete()`].
+    pub fn delete(&self, root: &mut Value, expression: &str) -> Result<usize, RuntimeError> {
+        let ast = parse(expression)?;
+        mutation::delete(self, root, &ast)
+    }
+    /// Defines a JMESPath function whose implementation is a JMESPath expression.
+    ///
+    /// The definition names the function and its parameters, followed by
+    /// the expression that makes up the body of the function.
+    /// Parameters are bound as variables when the function is invoked.
+    ///
+    /// See [`UserFunction`] for more details.
+    ///
+    /// # Example
+    /// ```
+    /// use jmespath_community as jmespath;
+    /// use jmespath::{Runtime, Value};
+    ///
+    /// let mut runtime = Runtime::create_runtime();
+    /// runtime.define("is_adult($p) = $p.age >= `18`").unwrap();
+    ///
+    /// let data = Value::from_json(r#"[{"name": "Ada", "age": 36}, {"name": "Alan", "age": 12}]"#).unwrap();
+    /// let result = runtime.search("[?is_adult(@)].name", &data).unwrap();
+    /// assert_eq!(Value::from_json(r#"["Ada"]"#).unwrap(), result);
+    /// ```
+    pub fn define(&mut self, definition: &str) -> Result<(), RuntimeError> {
+        let function = UserFunction::parse(definition)?;
+        self.register(Box::new(function));
+        Ok(())
+    }
+    /// Sets a global variable, available to all expressions evaluated by this [`Runtime`].
+    ///
+    /// The variable name may omit its leading `$`. Global variables are
+    /// also in scope in the body of functions defined with [`Runtime::define()`].
+    ///
+    /// # Example
+    /// ```
+    /// use jmespath_community as jmespath;
+    /// use jmespath::{Runtime, Value};
+    ///
+    /// let mut runtime = Runtime::create_runtime();
+    /// runtime.set_global("$threshold", 18);
+    ///
+    /// let data = Value::from_json(r#"[{"name": "Ada", "age": 36}, {"name": "Alan", "age": 12}]"#).unwrap();
+    /// let result = runtime.search("[?age >= $threshold].name", &data).unwrap();
+    /// assert_eq!(Value::from_json(r#"["Ada"]"#).unwrap(), result);
+    /// ```
+    pub fn set_global(&mut self, name: &str, value: impl Into<Value>) {
+        self.globals.insert(variable_ref(name), value.into());
+    }
+    /// Retrieves the value of a global variable, by its name including the leading `$`.
+    pub(crate) fn get_global(&self, variable_ref: &str) -> Option<&Value> {
+        self.globals.get(variable_ref)
+    }
     /// Evaluates a parsed JMESPath expression.
     pub fn search_ast(&self, ast: &AST, root: &Value) -> ReturnValue {
         let interpreter = Interpreter::new(self, root);
         interpreter.evaluate(&ast)
     }
+    /// Renders an [`Error`](RuntimeError) raised while evaluating a JMESPath
+    /// expression as a human-readable diagnostic message.
+    ///
+    /// Unlike [`RuntimeError::render()`], hints for unknown functions
+    /// take into account custom functions registered with this [`Runtime`].
+    pub fn render_error(&self, error: &RuntimeError, expression: &str) -> String {
+        let registry = match &self.registry {
+            Some(registry) => registry,
+            None => self.shared_registry,
+        };
+        Diagnostic::new(error, expression)
+            .suggest_functions(registry.function_names())
+            .render()
+    }
     pub(crate) fn call(
         &self,
         fname: &str,
@@ -187,7 +401,7 @@ impl Runtime {
 
         while {
             last_index += 1;
-            last_index < args.len() - 1
+            last_index + 1 < args.len()
         } {
             assert!(param.is_some());
             assert!(matches!(param.unwrap(), Parameter::Variadic(..)));
@@ -366,6 +580,24 @@ mod tests {
                 closure: Box::new(closure),
             })
         }
+        fn invoke(&self, _: &crate::AST, _: &[crate::Value]) -> crate::functions::ReturnValue {
+            unimplemented!()
+        }
+        fn invoke_with_bindings(
+            &self,
+            _: &crate::AST,
+            _: &[crate::Value],
+            _: &crate::Map<String, crate::Value>,
+        ) -> crate::functions::ReturnValue {
+            unimplemented!()
+        }
+        fn evaluate_with_bindings(
+            &self,
+            _: &crate::AST,
+            _: &crate::Map<String, crate::Value>,
+        ) -> crate::functions::ReturnValue {
+            unimplemented!()
+        }
     }
 
     fn setup() -> Fixture {
@@ -449,6 +681,45 @@ mod tests {
         assert!(matches!(result, Err(UnknownFunction)));
     }
 
+    #[test]
+    fn render_error_suggests_custom_function() {
+        let fixture = setup();
+        let expression = "summ(`1`, `2`)";
+        let err = fixture
+            .runtime
+            .search(expression, &Value::Null)
+            .unwrap_err();
+        let rendered = fixture.runtime.render_error(&err, expression);
+        assert!(rendered.ends_with("= help: did you mean `sum`?"));
+    }
+
+    #[rstest::rstest]
+    #[case("[$user, $role]", r#"["ada", "admin"]"#)]
+    #[case("let $user = 'alan' in [$user, $role]", r#"["alan", "admin"]"#)]
+    #[case("whoami()", r#""nobody""#)]
+    fn search_with_bindings_and_globals(#[case] expression: &str, #[case] expected: &str) {
+        let mut runtime = Runtime::create_runtime();
+        runtime.set_global("role", "admin");
+        runtime.set_global("$user", "nobody");
+        runtime.define("whoami() = $user").unwrap();
+
+        let bindings = crate::map!("user".to_string() => "ada".into());
+        let result = runtime
+            .search_with(expression, &Value::Null, bindings)
+            .unwrap();
+        assert_eq!(Value::from_json(expected).unwrap(), result);
+    }
+
+    #[test]
+    fn search_with_undefined_variable() {
+        let runtime = Runtime::create_runtime();
+        let bindings = crate::map!("$user".to_string() => "ada".into());
+        let err = runtime
+            .search_with("$users", &Value::Null, bindings)
+            .unwrap_err();
+        assert_eq!(UndefinedVariable, err.kind);
+    }
+
     #[test]
     fn invalid_arity_too_few_arguments() {
         let fixture = setup();
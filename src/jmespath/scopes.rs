@@ -1,6 +1,12 @@
 use crate::Map;
 use crate::Value;
 
+/// Returns the name of a variable as referenced in an expression,
+/// _i.e_ with its leading `$`.
+pub(crate) fn variable_ref(name: &str) -> String {
+    format!("${}", name.strip_prefix('$').unwrap_or(name))
+}
+
 #[derive(Debug)]
 pub(crate) struct Scopes<'a> {
     bindings: &'a Map<String, Value>,
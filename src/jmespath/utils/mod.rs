@@ -1,5 +1,6 @@
 pub(crate) mod map;
 pub(crate) mod serde;
+pub(crate) mod serializer;
 
 mod number;
 
@@ -1,9 +1,18 @@
-use crate::{Error, errors::Kind, value_eq::float_eq};
+use crate::{Error, errors::Kind};
 
-/// Represents a JSON [`f64`] number that can be safely ordered.
-#[derive(Debug, Copy, Clone, PartialEq)]
+/// Represents a JSON number that can be safely ordered.
+///
+/// Integers within the range of [`i64`] and [`u64`] are kept exactly,
+/// so that large identifiers survive a round-trip through a [`crate::Value`].
+/// Other numbers are represented as an [`f64`].
+#[derive(Debug, Copy, Clone)]
 pub struct Number {
-    pub(crate) number: f64,
+    repr: Repr,
+}
+#[derive(Debug, Copy, Clone)]
+enum Repr {
+    Integer(i128),
+    Float(f64),
 }
 impl Number {
     /// Creates a new instance of the [`Number`] type.
@@ -20,18 +29,64 @@ impl Number {
                 "An invalid number was specified.",
             ));
         }
-        Ok(Number { number })
+        Ok(Number {
+            repr: Repr::Float(number),
+        })
+    }
+    /// Creates an exact integer [`Number`].
+    ///
+    /// Integers outside the range of [`i64`] and [`u64`]
+    /// are converted to an [`f64`].
+    pub(crate) fn from_i128(integer: i128) -> Self {
+        if integer < i64::MIN as i128 || integer > u64::MAX as i128 {
+            return Number {
+                repr: Repr::Float(integer as f64),
+            };
+        }
+        Number {
+            repr: Repr::Integer(integer),
+        }
+    }
+    /// Returns the value of this number as an [`f64`], possibly losing precision.
+    pub fn as_f64(&self) -> f64 {
+        match self.repr {
+            Repr::Integer(integer) => integer as f64,
+            Repr::Float(number) => number,
+        }
+    }
+    /// Returns the value of this number if it is an integer that fits in an [`i64`].
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_i128()
+            .and_then(|integer| i64::try_from(integer).ok())
+    }
+    /// Returns the value of this number if it is an integer that fits in a [`u64`].
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_i128()
+            .and_then(|integer| u64::try_from(integer).ok())
+    }
+    /// Returns `true` if this number is kept as an exact integer.
+    pub fn is_integer(&self) -> bool {
+        matches!(self.repr, Repr::Integer(..))
+    }
+    pub(crate) fn as_i128(&self) -> Option<i128> {
+        match self.repr {
+            Repr::Integer(integer) => Some(integer),
+            Repr::Float(..) => None,
+        }
     }
 }
 
 impl std::fmt::Display for Number {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let number = if float_eq(self.number, self.number.floor()) {
-            self.number.floor()
-        } else {
-            self.number
-        };
-        write!(f, "{}", number)
+        // floats are written the way serde writes them, _e.g_ `42.0`,
+        // so that they do not parse back as an integer
+        match self.repr {
+            Repr::Integer(integer) => write!(f, "{}", integer),
+            Repr::Float(number) => match serde_json::Number::from_f64(number) {
+                Some(number) => write!(f, "{}", number),
+                None => write!(f, "{}", number),
+            },
+        }
     }
 }
 
@@ -39,7 +94,21 @@ macro_rules! eq {
     ($type:ty) => {
         impl PartialEq<$type> for Number {
             fn eq(&self, other: &$type) -> bool {
-                self.number == *other as f64
+                *self == Number::from_i128(*other as i128)
+            }
+        }
+        impl PartialEq<Number> for $type {
+            fn eq(&self, other: &Number) -> bool {
+                other == self
+            }
+        }
+    };
+}
+macro_rules! eq_float {
+    ($type:ty) => {
+        impl PartialEq<$type> for Number {
+            fn eq(&self, other: &$type) -> bool {
+                self.as_f64() == *other as f64
             }
         }
         impl PartialEq<Number> for $type {
@@ -63,16 +132,14 @@ eq!(u64);
 eq!(isize);
 eq!(usize);
 
-eq!(f32);
-eq!(f64);
+eq_float!(f32);
+eq_float!(f64);
 
 macro_rules! from {
     ($type:ty) => {
         impl From<$type> for Number {
             fn from(value: $type) -> Self {
-                Number {
-                    number: value as f64,
-                }
+                Number::from_i128(value as i128)
             }
         }
     };
@@ -93,15 +160,20 @@ from!(usize);
 
 impl From<Number> for f64 {
     fn from(value: Number) -> Self {
-        value.number
+        value.as_f64()
     }
 }
 impl From<&Number> for f64 {
     fn from(value: &Number) -> Self {
-        value.number
+        value.as_f64()
     }
 }
 
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
 impl PartialOrd for Number {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
@@ -110,9 +182,39 @@ impl PartialOrd for Number {
 impl Eq for Number {}
 impl Ord for Number {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        assert!(self.number.is_finite());
-        assert!(!self.number.is_nan());
-        self.number.partial_cmp(&other.number).unwrap()
+        match (self.repr, other.repr) {
+            (Repr::Integer(left), Repr::Integer(right)) => left.cmp(&right),
+            (Repr::Integer(left), Repr::Float(right)) => cmp_integer_float(left, right),
+            (Repr::Float(left), Repr::Integer(right)) => cmp_integer_float(right, left).reverse(),
+            (Repr::Float(left), Repr::Float(right)) => {
+                assert!(left.is_finite() && right.is_finite());
+                left.partial_cmp(&right).unwrap()
+            }
+        }
+    }
+}
+
+/// Compares an integer with a finite float exactly.
+///
+/// The integer is compared with the integral part of the float first,
+/// then with its fractional part, so that no precision is lost
+/// converting either side.
+fn cmp_integer_float(integer: i128, float: f64) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+
+    assert!(float.is_finite());
+    // 2^127, the first integral float outside the range of i128
+    const BOUND: f64 = 170_141_183_460_469_231_731_687_303_715_884_105_728.0;
+    let integral = float.trunc();
+    if integral >= BOUND {
+        return Ordering::Less;
+    }
+    if integral < -BOUND {
+        return Ordering::Greater;
+    }
+    match integer.cmp(&(integral as i128)) {
+        Ordering::Equal => 0.0.partial_cmp(&(float - integral)).unwrap(),
+        ordering => ordering,
     }
 }
 
@@ -131,8 +233,9 @@ mod tests {
     }
 
     #[rstest]
-    #[case("42", Number::from(42.0).unwrap())]
+    #[case("42.0", Number::from(42.0).unwrap())]
     #[case("42.1", Number::from(42.10).unwrap())]
+    #[case("42", Number::from_i128(42))]
     fn it_implements_display_trait(#[case] expected: &str, #[case] number: Number) {
         assert_eq!(expected, format!("{}", number));
     }
@@ -209,4 +312,32 @@ mod tests {
     ) {
         assert_eq!(Some(expected), left.partial_cmp(&right))
     }
+
+    #[rstest]
+    #[case(Greater, Number::from_i128(9007199254740993), Number::from(9007199254740992.0).unwrap())]
+    #[case(Equal, Number::from_i128(9007199254740992), Number::from(9007199254740992.0).unwrap())]
+    #[case(Less, Number::from_i128(42), Number::from(42.5).unwrap())]
+    #[case(Greater, Number::from_i128(-42), Number::from(-42.5).unwrap())]
+    #[case(Greater, Number::from_i128(43), Number::from(42.5).unwrap())]
+    #[case(Less, Number::from_i128(u64::MAX as i128), Number::from(1e300).unwrap())]
+    #[case(Greater, Number::from_i128(i64::MIN as i128), Number::from(-1e300).unwrap())]
+    fn it_compares_integers_and_floats_exactly(
+        #[case] expected: Ordering,
+        #[case] integer: Number,
+        #[case] float: Number,
+    ) {
+        assert_eq!(expected, integer.cmp(&float));
+        assert_eq!(expected.reverse(), float.cmp(&integer));
+    }
+
+    #[test]
+    fn it_implements_a_total_order() {
+        let a = Number::from_i128(9007199254740993);
+        let f = Number::from(9007199254740992.0).unwrap();
+        let c = Number::from_i128(9007199254740992);
+        assert_ne!(a, f);
+        assert_eq!(f, c);
+        assert_ne!(a, c);
+        assert!(c < a && f < a);
+    }
 }
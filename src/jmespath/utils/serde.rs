@@ -1,4 +1,7 @@
-use crate::{utils::Number, value::Value};
+use serde::de::{MapAccess, SeqAccess, Visitor};
+use serde::ser::Error as _;
+
+use crate::{Map, errors::Kind, functions::DataType, utils::Number, value::Value};
 
 impl serde::Serialize for Value {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -12,16 +15,112 @@ impl serde::Serialize for Value {
             Self::Number(n) => n.serialize(serializer),
             Self::Object(o) => o.serialize(serializer),
             Self::String(s) => serializer.serialize_str(s),
-            _ => unreachable!(),
+            Self::Expression(..) => Err(S::Error::custom(
+                "a JMESPath expression cannot be serialized",
+            )),
         }
     }
 }
+impl<'de> serde::Deserialize<'de> for Value {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+struct ValueVisitor;
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("any valid JSON value")
+    }
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(Value::Boolean(v))
+    }
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(Value::Number(v.into()))
+    }
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(Value::Number(v.into()))
+    }
+    fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E> {
+        Ok(Value::Number(Number::from_i128(v)))
+    }
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Number::from(v).map(Value::Number).map_err(E::custom)
+    }
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Value::String(v.to_string()))
+    }
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+        Ok(Value::String(v))
+    }
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(Value::Null)
+    }
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(Value::Null)
+    }
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        serde::Deserialize::deserialize(deserializer)
+    }
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut array = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(element) = seq.next_element()? {
+            array.push(element);
+        }
+        Ok(Value::Array(array))
+    }
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut object = Map::new();
+        while let Some((key, value)) = map.next_entry::<String, Value>()? {
+            object.insert(key, value);
+        }
+        Ok(Value::Object(object))
+    }
+}
 impl serde::Serialize for Number {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.serialize_f64(self.number)
+        if let Some(integer) = self.as_i64() {
+            serializer.serialize_i64(integer)
+        } else if let Some(integer) = self.as_u64() {
+            serializer.serialize_u64(integer)
+        } else {
+            serializer.serialize_f64(self.as_f64())
+        }
+    }
+}
+impl serde::Serialize for Kind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+impl serde::Serialize for DataType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
     }
 }
 
@@ -33,10 +132,10 @@ mod tests {
     use rstest::*;
 
     #[rstest]
-    #[case(vec![1, 2, 3].into(), "[1.0,2.0,3.0]")]
+    #[case(vec![1, 2, 3].into(), "[1,2,3]")]
     #[case(true.into(), "true")]
     #[case(false.into(), "false")]
-    #[case(2.into(), "2.0")]
+    #[case(2.into(), "2")]
     #[case(None.into(), "null")]
     #[case(map!("foo"=>"bar").into(), "{\"foo\":\"bar\"}")]
     fn it_serializes_value_to_serde_json(#[case] arg: Value, #[case] expected: &str) {
@@ -44,14 +143,49 @@ mod tests {
     }
 
     #[rstest]
-    #[case(42i8.into(), "42.0")]
+    #[case(42i8.into(), "42")]
+    #[case(Value::from_f64(42.0).unwrap(), "42.0")]
+    #[case(u64::MAX.into(), "18446744073709551615")]
+    #[case(i64::MIN.into(), "-9223372036854775808")]
     fn it_serializes_number_to_serde_json(#[case] arg: Value, #[case] expected: &str) {
         assert_eq!(serde_json::to_string(&arg).unwrap(), expected);
     }
 
+    #[rstest]
+    #[case(r#""invalid-type""#, crate::errors::Kind::InvalidType)]
+    #[case(r#""not-a-number""#, crate::errors::Kind::NotANumber)]
+    fn it_serializes_kind_to_serde_json(#[case] expected: &str, #[case] kind: crate::errors::Kind) {
+        assert_eq!(serde_json::to_string(&kind).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(r#""array[any]""#, crate::functions::DataType::Array)]
+    #[case(r#""expression""#, crate::functions::DataType::ExpRef)]
+    fn it_serializes_data_type_to_serde_json(
+        #[case] expected: &str,
+        #[case] data_type: crate::functions::DataType,
+    ) {
+        assert_eq!(serde_json::to_string(&data_type).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(r#"[1,-2,1.5,true,null,"text",{"foo":[]}]"#)]
+    #[case("18446744073709551615")]
+    #[case("-9223372036854775808")]
+    fn it_deserializes_value_from_serde_json(#[case] json: &str) {
+        let value: Value = serde_json::from_str(json).unwrap();
+        assert_eq!(json, value.to_json());
+    }
+
+    #[test]
+    fn it_fails_to_serialize_expression() {
+        let value = Value::Expression(crate::parse("foo").unwrap());
+        assert!(serde_json::to_string(&value).is_err());
+    }
+
     #[test]
     fn it_serializes_heterogeneous_array() {
-        let expected = "[[],true,false,null,1.0,{}]";
+        let expected = "[[],true,false,null,1,{}]";
         let map: Map<&str, Value> = Map::new();
         let arg = vec![
             Value::Array(vec![]),
@@ -0,0 +1,521 @@
+use serde::Serialize;
+use serde::ser::{self, Impossible};
+
+use crate::errors::Kind;
+use crate::utils::Number;
+use crate::{Error, Map, Value};
+
+impl ser::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Error::new(Kind::InvalidValue, &msg.to_string())
+    }
+}
+
+/// A [`serde::Serializer`] that produces a [`Value`].
+///
+/// Enums are represented the same way as `serde_json` does,
+/// _i.e_ externally tagged.
+pub(crate) struct ValueSerializer;
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeTupleVariant;
+    type SerializeMap = SerializeObject;
+    type SerializeStruct = SerializeObject;
+    type SerializeStructVariant = SerializeStructVariant;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, Error> {
+        Ok(Value::Boolean(v))
+    }
+    fn serialize_i8(self, v: i8) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+    fn serialize_i16(self, v: i16) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+    fn serialize_i32(self, v: i32) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+    fn serialize_i64(self, v: i64) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+    fn serialize_i128(self, v: i128) -> Result<Value, Error> {
+        Ok(Value::Number(Number::from_i128(v)))
+    }
+    fn serialize_u8(self, v: u8) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+    fn serialize_u16(self, v: u16) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+    fn serialize_u32(self, v: u32) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+    fn serialize_u64(self, v: u64) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+    fn serialize_u128(self, v: u128) -> Result<Value, Error> {
+        match i128::try_from(v) {
+            Ok(v) => self.serialize_i128(v),
+            Err(_) => self.serialize_f64(v as f64),
+        }
+    }
+    fn serialize_f32(self, v: f32) -> Result<Value, Error> {
+        self.serialize_f64(v as f64)
+    }
+    fn serialize_f64(self, v: f64) -> Result<Value, Error> {
+        Value::from_f64(v)
+    }
+    fn serialize_char(self, v: char) -> Result<Value, Error> {
+        Ok(Value::String(v.to_string()))
+    }
+    fn serialize_str(self, v: &str) -> Result<Value, Error> {
+        Ok(Value::String(v.to_string()))
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, Error> {
+        Ok(Value::Array(v.iter().map(|byte| (*byte).into()).collect()))
+    }
+    fn serialize_none(self) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Value, Error> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value, Error> {
+        Ok(Value::String(variant.to_string()))
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value, Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, Error> {
+        let mut object = Map::new();
+        object.insert(variant.to_string(), value.serialize(self)?);
+        Ok(Value::Object(object))
+    }
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray, Error> {
+        Ok(SerializeArray {
+            array: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray, Error> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, Error> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeTupleVariant, Error> {
+        Ok(SerializeTupleVariant {
+            variant: variant.to_string(),
+            array: Vec::with_capacity(len),
+        })
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeObject, Error> {
+        Ok(SerializeObject {
+            object: Map::new(),
+            key: None,
+        })
+    }
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeObject, Error> {
+        self.serialize_map(Some(len))
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeStructVariant, Error> {
+        Ok(SerializeStructVariant {
+            variant: variant.to_string(),
+            object: Map::new(),
+        })
+    }
+}
+
+pub(crate) struct SerializeArray {
+    array: Vec<Value>,
+}
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.array.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Array(self.array))
+    }
+}
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<Value, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<Value, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+pub(crate) struct SerializeTupleVariant {
+    variant: String,
+    array: Vec<Value>,
+}
+impl ser::SerializeTupleVariant for SerializeTupleVariant {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.array.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+    fn end(self) -> Result<Value, Error> {
+        let mut object = Map::new();
+        object.insert(self.variant, Value::Array(self.array));
+        Ok(Value::Object(object))
+    }
+}
+
+pub(crate) struct SerializeObject {
+    object: Map<String, Value>,
+    key: Option<String>,
+}
+impl ser::SerializeMap for SerializeObject {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(key.serialize(MapKeySerializer)?);
+        Ok(())
+    }
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .expect("serialize_value called before serialize_key");
+        self.object.insert(key, value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Object(self.object))
+    }
+}
+impl ser::SerializeStruct for SerializeObject {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.object
+            .insert(key.to_string(), value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Object(self.object))
+    }
+}
+
+pub(crate) struct SerializeStructVariant {
+    variant: String,
+    object: Map<String, Value>,
+}
+impl ser::SerializeStructVariant for SerializeStructVariant {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.object
+            .insert(key.to_string(), value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+    fn end(self) -> Result<Value, Error> {
+        let mut object = Map::new();
+        object.insert(self.variant, Value::Object(self.object));
+        Ok(Value::Object(object))
+    }
+}
+
+// object keys must be strings, but numbers and characters
+// are accepted and converted, as `serde_json` does
+struct MapKeySerializer;
+
+fn key_must_be_a_string() -> Error {
+    Error::new(Kind::InvalidType, "an object key must be a string")
+}
+
+impl ser::Serializer for MapKeySerializer {
+    type Ok = String;
+    type Error = Error;
+
+    type SerializeSeq = Impossible<String, Error>;
+    type SerializeTuple = Impossible<String, Error>;
+    type SerializeTupleStruct = Impossible<String, Error>;
+    type SerializeTupleVariant = Impossible<String, Error>;
+    type SerializeMap = Impossible<String, Error>;
+    type SerializeStruct = Impossible<String, Error>;
+    type SerializeStructVariant = Impossible<String, Error>;
+
+    fn serialize_bool(self, _v: bool) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+    fn serialize_i8(self, v: i8) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_i16(self, v: i16) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_i32(self, v: i32) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_i64(self, v: i64) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_i128(self, v: i128) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_u8(self, v: u8) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_u16(self, v: u16) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_u32(self, v: u32) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_u64(self, v: u64) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_u128(self, v: u128) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_f32(self, _v: f32) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+    fn serialize_f64(self, _v: f64) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+    fn serialize_char(self, v: char) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_str(self, v: &str) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+    fn serialize_none(self) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, _value: &T) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+    fn serialize_unit(self) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<String, Error> {
+        Ok(variant.to_string())
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(key_must_be_a_string())
+    }
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(key_must_be_a_string())
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(key_must_be_a_string())
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(key_must_be_a_string())
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(key_must_be_a_string())
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Err(key_must_be_a_string())
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(key_must_be_a_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Value;
+    use crate::errors::Kind;
+    use std::collections::BTreeMap;
+
+    #[derive(serde::Serialize)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Point(i32, i32),
+        Rectangle { width: u32, height: u32 },
+    }
+
+    #[derive(serde::Serialize)]
+    struct Config {
+        id: u64,
+        name: String,
+        enabled: bool,
+        ratio: Option<f32>,
+        tags: Vec<&'static str>,
+        shapes: Vec<Shape>,
+        limits: BTreeMap<u8, char>,
+        unit: (),
+    }
+
+    #[test]
+    fn it_serializes_to_value() {
+        let config = Config {
+            id: u64::MAX,
+            name: "main".to_string(),
+            enabled: true,
+            ratio: None,
+            tags: vec!["a", "b"],
+            shapes: vec![
+                Shape::Empty,
+                Shape::Circle(0.5),
+                Shape::Point(1, -2),
+                Shape::Rectangle {
+                    width: 3,
+                    height: 4,
+                },
+            ],
+            limits: BTreeMap::from([(1, 'x')]),
+            unit: (),
+        };
+        let value = Value::from_serialize(&config).unwrap();
+        let expected = Value::from_json(
+            r#"{
+                "id": 18446744073709551615,
+                "name": "main",
+                "enabled": true,
+                "ratio": null,
+                "tags": ["a", "b"],
+                "shapes": [
+                    "Empty",
+                    {"Circle": 0.5},
+                    {"Point": [1, -2]},
+                    {"Rectangle": {"width": 3, "height": 4}}
+                ],
+                "limits": {"1": "x"},
+                "unit": null
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(expected, value);
+        assert_eq!(
+            Some(u64::MAX),
+            value.as_object().unwrap()["id"]
+                .as_number()
+                .unwrap()
+                .as_u64()
+        );
+    }
+
+    #[test]
+    fn it_fails_on_invalid_number() {
+        let err = Value::from_serialize(&vec![f64::NAN]).unwrap_err();
+        assert_eq!(Kind::NotANumber, err.kind);
+    }
+
+    #[test]
+    fn it_fails_on_invalid_key() {
+        let map = BTreeMap::from([(vec![1], 1)]);
+        let err = Value::from_serialize(&map).unwrap_err();
+        assert_eq!(Kind::InvalidType, err.kind);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::errors::Kind;
 use crate::functions::DataType;
 use crate::parser::AST;
 use crate::utils::Number;
@@ -154,7 +155,12 @@ impl Value {
             serde_json::Value::Bool(b) => Value::Boolean(*b),
             serde_json::Value::Null => Value::Null,
             serde_json::Value::Number(n) => {
-                let num = Number::from(n.as_f64().unwrap()).unwrap();
+                // integers are kept exactly
+                let num = match (n.as_i64(), n.as_u64()) {
+                    (Some(integer), _) => integer.into(),
+                    (_, Some(integer)) => integer.into(),
+                    _ => Number::from(n.as_f64().unwrap()).unwrap(),
+                };
                 Value::Number(num)
             }
             serde_json::Value::String(s) => Value::String(s.to_string()),
@@ -183,20 +189,57 @@ impl Value {
     /// ```
     ///
     pub fn from_json(text: &str) -> Result<Self, serde_json::Error> {
-        match text {
-            "null" => Ok(Value::Null),
-            _ => {
-                let obj: Result<serde_json::Value, serde_json::Error> = serde_json::from_str(text);
-                match obj {
-                    Err(err) => Err(err),
-                    Ok(v) => Ok(Self::map_from_json(&v)),
-                }
-            }
-        }
+        serde_json::from_str(text)
+    }
+    /// Creates a [`Value`] from any type that implements [`serde::Serialize`].
+    ///
+    /// This does not go through an intermediate JSON representation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jmespath_community as jmespath;
+    /// use jmespath::Value;
+    ///
+    /// #[derive(serde::Serialize)]
+    /// struct Account {
+    ///     id: u64,
+    ///     name: String,
+    /// }
+    ///
+    /// let account = Account { id: 1234567890123456789, name: "main".to_string() };
+    /// let value = Value::from_serialize(&account).unwrap();
+    /// assert_eq!(r#"{"id":1234567890123456789,"name":"main"}"#, value.to_json());
+    /// ```
+    pub fn from_serialize<T: serde::Serialize + ?Sized>(value: &T) -> Result<Self, Error> {
+        value.serialize(crate::utils::serializer::ValueSerializer)
+    }
+    /// Converts this [`Value`] into any type that implements [`serde::de::DeserializeOwned`].
+    ///
+    /// Returns an [`Error`] of kind [`InvalidType`](crate::errors::Kind::InvalidType)
+    /// if this [`Value`] does not match the shape of the target type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jmespath_community as jmespath;
+    /// use jmespath::Value;
+    ///
+    /// let value = Value::from_json("[1, 2, 3]").unwrap();
+    /// let numbers: Vec<u8> = value.deserialize_into().unwrap();
+    /// assert_eq!(vec![1, 2, 3], numbers);
+    /// ```
+    pub fn deserialize_into<R: serde::de::DeserializeOwned>(&self) -> Result<R, Error> {
+        let json = serde_json::Value::try_from(self)?;
+        serde_json::from_value(json).map_err(|err| Error::new(Kind::InvalidType, &err.to_string()))
     }
     /// Returns the JSON string representation
     /// for this [`Value`].
     ///
+    /// # Panics
+    ///
+    /// Panics if this [`Value`] is or contains a [`Value::Expression`].
+    ///
     /// # Example
     ///
     /// ```
@@ -274,8 +317,8 @@ impl Value {
 
     /// Returns an [`Option<f64>`] of the corresponding type.
     pub fn as_f64(&self) -> Option<f64> {
-        if let Self::Number(Number { number: token }) = self {
-            Some(*token)
+        if let Self::Number(number) = self {
+            Some(number.as_f64())
         } else {
             None
         }
@@ -363,11 +406,12 @@ mod tests {
     use rstest::*;
 
     #[rstest]
-    #[case("[1.0,2.0,3.0]", Value::Array(vec![Value::Number(1i8.into()), Value::Number(2i8.into()), Value::Number(3i8.into())]))]
+    #[case("[1,2,3]", Value::Array(vec![Value::Number(1i8.into()), Value::Number(2i8.into()), Value::Number(3i8.into())]))]
     #[case("true", Value::Boolean(true))]
     #[case("false", Value::Boolean(false))]
     #[case("null", Value::Null)]
-    #[case("42.0", Value::Number(42i8.into()))]
+    #[case("42", Value::Number(42i8.into()))]
+    #[case("42.0", Value::from_f64(42.0).unwrap())]
     #[case(r#"{"foo":"bar"}"#, Value::Object(map!("foo".to_string() => Value::String("bar".to_string()))))]
     #[case(r#""foo""#, Value::String("foo".to_string()))]
     fn it_implements_display_trait(#[case] expected: &str, #[case] value: Value) {
@@ -416,11 +460,13 @@ mod tests {
     }
 
     #[rstest]
-    #[case("[1.0,2.0,3.0]", vec![1i8, 2i8, 3i8].into())]
+    #[case("[1,2,3]", vec![1i8, 2i8, 3i8].into())]
     #[case("true", true.into())]
     #[case("false", false.into())]
     #[case("null", Value::Null)]
-    #[case("1.0", 1i8.into())]
+    #[case("1", 1i8.into())]
+    #[case("1.5", Value::from_f64(1.5).unwrap())]
+    #[case("18446744073709551615", u64::MAX.into())]
     #[case(r#"{"foo":"bar"}"#, map!("foo" => "bar").into())]
     #[case("\"text\"", "text".into())]
     fn to_json(#[case] expected: &str, #[case] input: Value) {
@@ -1,4 +1,4 @@
-use crate::{Value, utils::Number};
+use crate::Value;
 
 impl Eq for Value {}
 impl PartialEq for Value {
@@ -8,9 +8,11 @@ impl PartialEq for Value {
             Value::Null => other.is_null(),
             Value::Array(a) => other.is_array() && Some(a) == other.as_array(),
             Value::Boolean(b) => other.is_bool() && Some(*b) == other.as_bool(),
-            Value::Number(Number { number: n }) => {
-                other.is_number() && float_eq(*n, other.as_number().unwrap().into())
-            }
+            Value::Number(n) => match other.as_number() {
+                Some(o) if n.is_integer() || o.is_integer() => n == o,
+                Some(o) => float_eq(n.as_f64(), o.as_f64()),
+                None => false,
+            },
             Value::String(s) => other.is_str() && s == other.as_str().unwrap(),
             Value::Object(o) => other.is_object() && Some(o) == other.as_object(),
         }
@@ -138,6 +140,8 @@ mod tests {
     #[case(Value::Null, None.into(), true)]
     #[case(Value::from_json(r#"{"foo": "bar"}"#).unwrap(), map!("foo" => "bar").into(), true)]
     #[case(Value::from_json(r#"["one", 2, {"three": 3}]"#).unwrap(), vec!["one".into(), <i32 as Into<Value>>::into(2), map!("three" => 3).into()].into(), true)]
+    #[case(Value::from(9007199254740993u64), Value::from_f64(9007199254740992.0).unwrap(), false)]
+    #[case(Value::from(9007199254740992u64), Value::from_f64(9007199254740992.0).unwrap(), true)]
     fn it_implements_partial_eq(#[case] left: Value, #[case] right: Value, #[case] expected: bool) {
         assert_eq!(expected, left == right);
     }
@@ -1,5 +1,6 @@
+use crate::errors::Kind;
 use crate::utils::Number;
-use crate::{Map, Value};
+use crate::{Error, Map, Value};
 
 impl<V> From<Map<&str, V>> for Value
 where
@@ -46,15 +47,6 @@ macro_rules! from_ {
         }
     };
 }
-macro_rules! from_as {
-    ($ident:ty, $as:ty, $value:ident) => {
-        impl From<$ident> for Value {
-            fn from(v: $ident) -> Self {
-                Self::$value(Number::from((v as $as).into()).unwrap())
-            }
-        }
-    };
-}
 
 from_! {bool, Boolean}
 from_! {i8, Number}
@@ -63,9 +55,10 @@ from_! {i32, Number}
 from_! {u8, Number}
 from_! {u16, Number}
 from_! {u32, Number}
-
-from_as! {isize, f64, Number}
-from_as! {usize, f64, Number}
+from_! {i64, Number}
+from_! {u64, Number}
+from_! {isize, Number}
+from_! {usize, Number}
 
 impl From<&str> for Value {
     fn from(v: &str) -> Self {
@@ -83,9 +76,37 @@ impl From<&std::string::String> for Value {
     }
 }
 
+impl From<serde_json::Value> for Value {
+    fn from(v: serde_json::Value) -> Self {
+        Self::map_from_json(&v)
+    }
+}
+impl From<&serde_json::Value> for Value {
+    fn from(v: &serde_json::Value) -> Self {
+        Self::map_from_json(v)
+    }
+}
+
+/// Converts a [`Value`] to a [`serde_json::Value`].
+///
+/// This fails if the [`Value`] is or contains a [`Value::Expression`].
+impl TryFrom<Value> for serde_json::Value {
+    type Error = Error;
+    fn try_from(v: Value) -> Result<Self, Self::Error> {
+        serde_json::Value::try_from(&v)
+    }
+}
+impl TryFrom<&Value> for serde_json::Value {
+    type Error = Error;
+    fn try_from(v: &Value) -> Result<Self, Self::Error> {
+        serde_json::to_value(v).map_err(|err| Error::new(Kind::InvalidType, &err.to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
+    use crate::errors::Kind;
     use crate::map;
     use crate::utils::Number;
     use crate::{Map, Value};
@@ -154,6 +175,50 @@ mod tests {
     from_int!(it_implements_from_i16, 42.0, i16, 42i16);
     from_int!(it_implements_from_i32, 42.0, i32, 42i32);
     from_int!(it_implements_from_isize, 42.0, isize, 42isize);
+    from_int!(it_implements_from_i64, 42.0, i64, 42i64);
+    from_int!(it_implements_from_u64, 42.0, u64, 42u64);
+
+    #[test]
+    fn it_keeps_large_integers_exactly() {
+        let value: Value = u64::MAX.into();
+        assert_eq!(Some(u64::MAX), value.as_number().unwrap().as_u64());
+        let value: Value = i64::MIN.into();
+        assert_eq!(Some(i64::MIN), value.as_number().unwrap().as_i64());
+    }
+
+    #[test]
+    fn it_implements_from_serde_json() {
+        let json = serde_json::json!({"id": 18446744073709551615u64, "tags": ["a"], "ratio": 0.5});
+        let value: Value = json.into();
+        let expected =
+            Value::from_json(r#"{"id": 18446744073709551615, "ratio": 0.5, "tags": ["a"]}"#)
+                .unwrap();
+        assert_eq!(expected, value);
+        assert_eq!(
+            Some(u64::MAX),
+            value.as_object().unwrap()["id"]
+                .as_number()
+                .unwrap()
+                .as_u64()
+        );
+    }
+    #[test]
+    fn it_implements_try_into_serde_json() {
+        let value = Value::from_json(r#"{"id": 9007199254740993, "tags": ["a", null]}"#).unwrap();
+        let json: serde_json::Value = (&value).try_into().unwrap();
+        assert_eq!(
+            serde_json::json!({"id": 9007199254740993u64, "tags": ["a", null]}),
+            json
+        );
+        let round_trip: Value = json.into();
+        assert_eq!(value, round_trip);
+    }
+    #[test]
+    fn it_fails_to_convert_expression_to_serde_json() {
+        let value = Value::Array(vec![Value::Expression(crate::parse("foo").unwrap())]);
+        let result: Result<serde_json::Value, _> = value.try_into();
+        assert_eq!(Kind::InvalidType, result.unwrap_err().kind);
+    }
 
     #[test]
     fn it_implements_from_refstr() {
@@ -26,11 +26,11 @@ fn main() {
     let value = Value::from_json(&buffer).unwrap();
     match jmespath::parse(expression) {
         Ok(ast) => println!("{}", ast),
-        Err(err) => println!("{}", err),
+        Err(err) => println!("{}", err.render(expression)),
     }
     let result = search(expression, &value);
     match result {
         Ok(v) => println!("{}", v.to_json()),
-        Err(err) => println!("{}", err),
+        Err(err) => println!("{}", err.render(expression)),
     }
 }